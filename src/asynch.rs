@@ -0,0 +1,278 @@
+use crate::instruction::Instruction;
+use crate::{InitStage, InitStep, PixelFormat, DEFAULT_INIT, GRAM_ROWS, ST7735};
+
+use core::future::poll_fn;
+use core::pin::pin;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use futures_core::Stream;
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Runs commands to initialize the display, waiting with an async delay.
+    ///
+    /// The commands are the same as `init`, but the reset and sleep-out waits yield to other
+    /// tasks instead of blocking.
+    pub async fn init_async<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init_async(delay, DEFAULT_INIT, &[]).await
+    }
+
+    /// Like `run_init_sequence`, waiting with an async delay.
+    pub async fn run_init_sequence_async<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        sequence: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init_async(delay, sequence, &[]).await
+    }
+
+    /// Like `init_with_extra`, waiting with an async delay.
+    pub async fn init_with_extra_async<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        extra: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init_async(delay, DEFAULT_INIT, extra).await
+    }
+
+    /// Runs all init stages, waiting between them with an async delay.
+    async fn run_init_async<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        sequence: &[InitStep],
+        extra: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayNs,
+    {
+        let mut stage = InitStage::Reset(0);
+        while stage != InitStage::Done {
+            let (wait, next) = self.init_stage(stage, sequence, extra, true)?;
+            if wait > 0 {
+                delay.delay_ms(u32::from(wait)).await;
+            }
+            stage = next;
+        }
+        Ok(())
+    }
+
+    /// Sets pixel colors at the given drawing window from an async stream.
+    ///
+    /// Colors are written as they arrive, buffered into small SPI writes, so an image can be
+    /// drawn progressively without holding it in memory. Returns an error if the stream ends
+    /// before the window is filled; the pixels received until then are still written.
+    pub async fn set_pixels_stream<S>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        stream: S,
+    ) -> Result<(), ()>
+    where
+        S: Stream<Item = u16>,
+    {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+
+        let expected = (u32::from(ex - sx) + 1) * (u32::from(ey - sy) + 1);
+        let mut stream = pin!(stream);
+        let mut buffer = [0; 32];
+        let mut index = 0;
+        let mut pending = None;
+        let mut written = 0;
+        // Colors are collected a row at a time when mirroring, so the row can be reversed.
+        let mut row = [0; GRAM_ROWS as usize];
+        let row_width = if self.mirror_x {
+            (usize::from(ex - sx) + 1).min(row.len())
+        } else {
+            1
+        };
+        let mut row_len = 0;
+        loop {
+            let color = if written < expected {
+                poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+            } else {
+                None
+            };
+            if let Some(color) = color {
+                row[row_len] = color;
+                row_len += 1;
+                written += 1;
+            }
+            if row_len == row_width || (color.is_none() && row_len > 0) {
+                for &color in row[0..row_len].iter().rev() {
+                    let (bytes, len) = self.encode_color(color, &mut pending);
+                    if index + len > buffer.len() {
+                        self.write_data(&buffer[0..index])?;
+                        index = 0;
+                    }
+                    buffer[index..index + len].copy_from_slice(&bytes[0..len]);
+                    index += len;
+                }
+                row_len = 0;
+            }
+            if color.is_none() {
+                break;
+            }
+        }
+        let (bytes, len) = PixelFormat::encode_pending(pending);
+        buffer[index..index + len].copy_from_slice(&bytes[0..len]);
+        index += len;
+        self.write_data(&buffer[0..index])?;
+
+        if written == expected {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Paces rendering to a fixed frame interval, e.g. to cap an animation at 30 frames per second.
+///
+/// Time is read from `now`, a free-running microsecond counter that may wrap around.
+pub struct FrameTimer<F>
+where
+    F: FnMut() -> u32,
+{
+    now: F,
+    interval_us: u32,
+    deadline: u32,
+}
+
+impl<F> FrameTimer<F>
+where
+    F: FnMut() -> u32,
+{
+    /// Creates a timer with frames of `interval_us` microseconds, the first one starting now.
+    pub fn new(mut now: F, interval_us: u32) -> Self {
+        let deadline = now().wrapping_add(interval_us);
+        FrameTimer {
+            now,
+            interval_us,
+            deadline,
+        }
+    }
+
+    /// Waits until the current frame's interval has passed.
+    ///
+    /// Frame boundaries are kept on a fixed schedule, so time spent drawing doesn't add up as
+    /// drift. When a frame overruns its budget this returns right away and the schedule restarts
+    /// from the current time, skipping the missed frames instead of rendering them back to back.
+    pub async fn wait_next_frame<DELAY>(&mut self, delay: &mut DELAY)
+    where
+        DELAY: DelayNs,
+    {
+        let now = (self.now)();
+        let remaining = self.deadline.wrapping_sub(now) as i32;
+        if remaining > 0 {
+            delay.delay_us(remaining as u32).await;
+            self.deadline = self.deadline.wrapping_add(self.interval_us);
+        } else {
+            self.deadline = now.wrapping_add(self.interval_us);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::instruction::Instruction;
+    use crate::mock::{self, block_on, Bus, Delay};
+    use crate::{InitStep, ST7735};
+
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    use futures_core::Stream;
+    use std::vec;
+
+    /// Stream that yields the items of an iterator right away.
+    struct IterStream<I>(I);
+
+    impl<I: Iterator + Unpin> Stream for IterStream<I> {
+        type Item = I::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    #[test]
+    fn set_pixels_stream_reverses_mirrored_rows() {
+        let (mut display, bus) = mock::display();
+        display.set_mirror_x(true);
+        block_on(display.set_pixels_stream(0, 0, 1, 1, IterStream(1..=4))).unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 126, 0, 127]));
+        assert_eq!(commands[2], (0x2C, vec![0, 2, 0, 1, 0, 4, 0, 3]));
+
+        bus.clear();
+        display.set_mirror_x(false);
+        assert_eq!(
+            block_on(display.set_pixels_stream(0, 0, 1, 1, IterStream(1..=3))),
+            Err(())
+        );
+        assert_eq!(bus.commands()[2], (0x2C, vec![0, 1, 0, 2, 0, 3]));
+    }
+
+    #[test]
+    fn init_async_matches_blocking_init() {
+        let extra = [InitStep {
+            cmd: Instruction::PWCTR6,
+            args: &[0x11, 0x15],
+            delay_ms: 5,
+        }];
+
+        let blocking = Bus::default();
+        let mut display = ST7735::new(
+            blocking.spi(),
+            blocking.dc(),
+            blocking.rst(),
+            true,
+            false,
+            128,
+            160,
+        );
+        display.set_init_settle_ms(2);
+        let mut blocking_delay = Delay::default();
+        display
+            .init_with_extra(&mut blocking_delay, &extra)
+            .unwrap();
+
+        let asynch = Bus::default();
+        let mut display = ST7735::new(
+            asynch.spi(),
+            asynch.dc(),
+            asynch.rst(),
+            true,
+            false,
+            128,
+            160,
+        );
+        display.set_init_settle_ms(2);
+        let mut async_delay = Delay::default();
+        block_on(display.init_with_extra_async(&mut async_delay, &extra)).unwrap();
+
+        assert_eq!(asynch.commands(), blocking.commands());
+        assert_eq!(asynch.reset_levels(), blocking.reset_levels());
+        assert_eq!(async_delay.waits, blocking_delay.waits);
+        assert!(display.is_initialized() && display.is_on());
+    }
+}
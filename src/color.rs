@@ -0,0 +1,76 @@
+//! Helpers for working with RGB565 colors.
+
+/// Scales every channel of a RGB565 color by `factor / 255`, rounding to the nearest value.
+pub const fn scale565(color: u16, factor: u8) -> u16 {
+    blend565(color, 0, factor)
+}
+
+/// Blends `foreground` over `background` with the given opacity, where 0 keeps `background` and
+/// 255 gives `foreground`. Every channel is rounded to the nearest value.
+pub const fn blend565(foreground: u16, background: u16, alpha: u8) -> u16 {
+    let (r1, g1, b1) = channels(foreground);
+    let (r2, g2, b2) = channels(background);
+    let alpha = alpha as u32;
+    pack(
+        blend_channel(r1, r2, alpha),
+        blend_channel(g1, g2, alpha),
+        blend_channel(b1, b2, alpha),
+    )
+}
+
+/// Adds two RGB565 colors channel by channel, saturating at the maximum of every channel.
+pub const fn add_sat565(a: u16, b: u16) -> u16 {
+    let (r1, g1, b1) = channels(a);
+    let (r2, g2, b2) = channels(b);
+    pack(min(r1 + r2, 0x1F), min(g1 + g2, 0x3F), min(b1 + b2, 0x1F))
+}
+
+/// Splits a RGB565 color into its 5, 6 and 5 bit channels.
+const fn channels(color: u16) -> (u32, u32, u32) {
+    (
+        (color >> 11) as u32,
+        ((color >> 5) & 0x3F) as u32,
+        (color & 0x1F) as u32,
+    )
+}
+
+/// Packs 5, 6 and 5 bit channels into a RGB565 color.
+const fn pack(r: u32, g: u32, b: u32) -> u16 {
+    (r << 11 | g << 5 | b) as u16
+}
+
+const fn blend_channel(foreground: u32, background: u32, alpha: u32) -> u32 {
+    (foreground * alpha + background * (255 - alpha) + 127) / 255
+}
+
+const fn min(a: u32, b: u32) -> u32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Converts a HSV color to RGB565 using integer math only.
+///
+/// `h` is the hue in degrees and wraps around at 360. `s` and `v` are the saturation and value,
+/// from 0 to 255.
+pub const fn hsv_to_rgb565(h: u16, s: u8, v: u8) -> u16 {
+    let h = (h % 360) as u32;
+    let s = s as u32;
+    let v = v as u32;
+    // Position within the current 60° sector, scaled to 0..=255.
+    let f = (h % 60) * 255 / 60;
+    let p = (v * (255 - s) + 127) / 255;
+    let q = (v * (255 - (s * f + 127) / 255) + 127) / 255;
+    let t = (v * (255 - (s * (255 - f) + 127) / 255) + 127) / 255;
+    let (r, g, b) = match h / 60 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+    pack(r >> 3, g >> 2, b >> 3)
+}
@@ -1,3 +1,16 @@
+/// MADCTL row address order (vertical mirror).
+pub const MADCTL_MY: u8 = 0x80;
+/// MADCTL column address order (horizontal mirror).
+pub const MADCTL_MX: u8 = 0x40;
+/// MADCTL row/column exchange.
+pub const MADCTL_MV: u8 = 0x20;
+/// MADCTL vertical refresh order, bottom line first.
+pub const MADCTL_ML: u8 = 0x10;
+/// MADCTL BGR color filter order.
+pub const MADCTL_BGR: u8 = 0x08;
+/// MADCTL horizontal refresh order, right to left.
+pub const MADCTL_MH: u8 = 0x04;
+
 /// ST7735 instructions.
 #[derive(Debug, Clone, Copy)]
 pub enum Instruction {
@@ -5,6 +18,11 @@ pub enum Instruction {
     SWRESET = 0x01,
     RDDID = 0x04,
     RDDST = 0x09,
+    RDDPM = 0x0A,
+    RDDMADCTL = 0x0B,
+    RDDCOLMOD = 0x0C,
+    RDDSM = 0x0E,
+    RDDSDR = 0x0F,
     SLPIN = 0x10,
     SLPOUT = 0x11,
     PTLON = 0x12,
@@ -18,6 +36,8 @@ pub enum Instruction {
     RAMWR = 0x2C,
     RAMRD = 0x2E,
     PTLAR = 0x30,
+    TEOFF = 0x34,
+    TEON = 0x35,
     COLMOD = 0x3A,
     MADCTL = 0x36,
     FRMCTR1 = 0xB1,
@@ -31,6 +51,8 @@ pub enum Instruction {
     PWCTR4 = 0xC3,
     PWCTR5 = 0xC4,
     VMCTR1 = 0xC5,
+    /// VCOM offset control, ST7735S only.
+    VMOFCTR = 0xC7,
     RDID1 = 0xDA,
     RDID2 = 0xDB,
     RDID3 = 0xDC,
@@ -1,15 +1,78 @@
 #![no_std]
+#![allow(clippy::result_unit_err)]
 
 //! This crate provides a ST7735 driver to connect to TFT displays.
+//!
+//! # Features
+//!
+//! - `graphics` (default): embedded-graphics `DrawTarget` support and the shape and sprite
+//!   helpers. Without it neither embedded-graphics nor micromath is compiled, leaving only
+//!   embedded-hal and nb as dependencies.
+//! - `font`: text drawing with embedded-graphics mono fonts, enables `graphics`.
+//! - `async`: `init_async` and the other async init methods, `set_pixels_stream` and `FrameTimer`.
+//! - `rle`: run-length encoded image drawing.
+//! - `stats`: bus traffic counters.
+//! - `serde`: serialization of `DisplayConfig` and `Orientation`.
 
+pub mod color;
 pub mod instruction;
 
-use crate::instruction::Instruction;
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "rle")]
+mod rle;
+#[cfg(feature = "graphics")]
+mod shapes;
+mod sized;
+#[cfg(feature = "graphics")]
+mod sprite;
+#[cfg(feature = "font")]
+mod text;
+mod writer;
+
+#[cfg(feature = "async")]
+pub use crate::asynch::FrameTimer;
+pub use crate::sized::ST7735Sized;
+pub use crate::writer::PixelWriter;
+
+use crate::instruction::{
+    Instruction, MADCTL_BGR, MADCTL_MH, MADCTL_ML, MADCTL_MV, MADCTL_MX, MADCTL_MY,
+};
+
+use core::convert::TryFrom;
 
 use embedded_hal::blocking::delay::DelayMs;
 use embedded_hal::blocking::spi;
 use embedded_hal::digital::v2::OutputPin;
 
+/// Number of columns in the ST7735 display RAM.
+const GRAM_COLUMNS: u32 = 132;
+
+/// Number of rows in the ST7735 display RAM.
+const GRAM_ROWS: u32 = 162;
+
+/// Normal mode power control (`PWCTR3`) parameters of `DEFAULT_INIT`.
+const NORMAL_MODE_POWER: &[u8] = &[0x0A, 0x00];
+
+/// Frame rate, power and VCOM register setup sent by `init`, before the inversion, orientation
+/// and pixel format commands that depend on the driver configuration.
+///
+/// Copy and adjust it to pass a modified sequence to `ST7735::run_init_sequence`.
+pub const DEFAULT_INIT: &[InitStep] = &[
+    InitStep::new(Instruction::FRMCTR1, &[0x01, 0x2C, 0x2D]),
+    InitStep::new(Instruction::FRMCTR2, &[0x01, 0x2C, 0x2D]),
+    InitStep::new(Instruction::FRMCTR3, &[0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D]),
+    InitStep::new(Instruction::INVCTR, &[0x07]),
+    InitStep::new(Instruction::PWCTR1, &[0xA2, 0x02, 0x84]),
+    InitStep::new(Instruction::PWCTR2, &[0xC5]),
+    InitStep::new(Instruction::PWCTR3, NORMAL_MODE_POWER),
+    InitStep::new(Instruction::PWCTR4, &[0x8A, 0x2A]),
+    InitStep::new(Instruction::PWCTR5, &[0x8A, 0xEE]),
+    InitStep::new(Instruction::VMCTR1, &[0x0E]),
+];
+
 /// ST7735 driver to connect to TFT displays.
 pub struct ST7735<SPI, DC, RST>
 where
@@ -23,8 +86,8 @@ where
     /// Data/command pin.
     dc: DC,
 
-    /// Reset pin.
-    rst: RST,
+    /// Reset pin, `None` if the display has no dedicated reset line.
+    rst: Option<RST>,
 
     /// Whether the display is RGB (true) or BGR (false)
     rgb: bool,
@@ -32,6 +95,67 @@ where
     /// Whether the colours are inverted (true) or not (false)
     inverted: bool,
 
+    /// Whether the reset pin is driven low to reset the controller
+    reset_active_low: bool,
+
+    /// Controller variant
+    variant: Variant,
+
+    /// Color depth of the pixel data sent to the display
+    pixel_format: PixelFormat,
+
+    /// Byte order of the colors passed to the driver
+    byte_order: ByteOrder,
+
+    /// Display orientation as last set by `set_orientation`
+    orientation: Orientation,
+
+    /// Whether the panel refreshes from the bottom line up (MADCTL ML)
+    line_order_reversed: bool,
+
+    /// Whether the panel refreshes each line from right to left (MADCTL MH)
+    refresh_order_reversed: bool,
+
+    /// Number of dummy bytes the controller sends before pixel data on a RAM read
+    read_dummy_bytes: u8,
+
+    /// Extra time to wait after every step of the init sequence, in milliseconds
+    init_settle_ms: u8,
+
+    /// Whether drawing is mirrored horizontally in software
+    mirror_x: bool,
+
+    /// Width of the current column window, used to reverse rows when mirroring
+    window_width: u16,
+
+    /// Window size in pixels from which `set_pixels` buffers its writes
+    buffering_threshold: u32,
+
+    /// Normal mode power control (`PWCTR3`) parameters restored by `power_save`
+    normal_mode_power: &'static [u8],
+
+    /// Whether `init` has completed
+    initialized: bool,
+
+    /// Whether the display is in sleep mode
+    sleeping: bool,
+
+    /// Whether the display output is turned on
+    display_on: bool,
+
+    /// Bus traffic counters
+    #[cfg(feature = "stats")]
+    stats: Stats,
+
+    /// Image offsets applied when switching to each orientation, see `set_offset_for`
+    orientation_offsets: [Option<(u16, u16)>; 4],
+
+    /// Full display size, while `width` and `height` are reduced to the viewport
+    display_size: (u32, u32),
+
+    /// Top left corner of the viewport within the display, see `set_viewport`
+    viewport_origin: (u16, u16),
+
     /// Global image offset
     dx: u16,
     dy: u16,
@@ -39,13 +163,306 @@ where
     height: u32,
 }
 
+/// Inclusive drawing window `(sx, sy, ex, ey)`.
+pub type Window = (u16, u16, u16, u16);
+
 /// Display orientation.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
 pub enum Orientation {
-    Portrait = 0x00,
-    Landscape = 0x60,
-    PortraitSwapped = 0xC0,
-    LandscapeSwapped = 0xA0,
+    Portrait = 0,
+    Landscape = MADCTL_MX | MADCTL_MV,
+    PortraitSwapped = MADCTL_MY | MADCTL_MX,
+    LandscapeSwapped = MADCTL_MY | MADCTL_MV,
+}
+
+impl Orientation {
+    /// Index of the orientation in per-orientation tables.
+    fn index(self) -> usize {
+        match self {
+            Orientation::Portrait => 0,
+            Orientation::Landscape => 1,
+            Orientation::PortraitSwapped => 2,
+            Orientation::LandscapeSwapped => 3,
+        }
+    }
+}
+
+/// Controller variant, used to restrict commands that only some variants support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// ST7735 or ST7735R.
+    ST7735,
+    /// ST7735S, which adds VCOM offset control.
+    ST7735S,
+}
+
+/// Display configuration that can be stored, e.g. in flash or EEPROM, and passed to
+/// `ST7735::from_config`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayConfig {
+    /// Display width in pixels.
+    pub width: u32,
+    /// Display height in pixels.
+    pub height: u32,
+    /// Horizontal offset of the image in the display RAM.
+    pub dx: u16,
+    /// Vertical offset of the image in the display RAM.
+    pub dy: u16,
+    /// Whether the display is RGB (true) or BGR (false).
+    pub rgb: bool,
+    /// Whether the colours are inverted.
+    pub inverted: bool,
+    /// Orientation applied by `init`.
+    pub orientation: Orientation,
+}
+
+impl DisplayConfig {
+    /// Configuration for panels that show the whole 132x162 display RAM, without an offset.
+    pub const FULL_GRAM: DisplayConfig = DisplayConfig {
+        width: GRAM_COLUMNS,
+        height: GRAM_ROWS,
+        dx: 0,
+        dy: 0,
+        rgb: true,
+        inverted: false,
+        orientation: Orientation::Portrait,
+    };
+}
+
+/// Bus traffic counters, collected when the `stats` feature is enabled.
+///
+/// The counters wrap around on overflow, so read them with `take_stats` often enough to not
+/// miss a wrap.
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of bytes written, including command bytes.
+    pub bytes: u32,
+    /// Number of commands sent.
+    pub commands: u32,
+}
+
+/// A single command of an initialization sequence.
+#[derive(Clone, Copy, Debug)]
+pub struct InitStep {
+    /// Command to send.
+    pub cmd: Instruction,
+    /// Parameters of the command.
+    pub args: &'static [u8],
+    /// Time to wait after the command, in milliseconds.
+    pub delay_ms: u8,
+}
+
+impl InitStep {
+    /// Creates a step that doesn't wait after the command.
+    pub const fn new(cmd: Instruction, args: &'static [u8]) -> Self {
+        InitStep {
+            cmd,
+            args,
+            delay_ms: 0,
+        }
+    }
+}
+
+/// Stage of the init sequence, see `ST7735::init_stage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InitStage {
+    /// Step of the reset pin pulse.
+    Reset(u8),
+    SoftwareReset,
+    SleepOut,
+    /// Step of the register setup sequence.
+    Sequence(usize),
+    /// Inversion, orientation and pixel format commands.
+    Config,
+    /// Step of the extra commands sent after the configuration.
+    Extra(usize),
+    DisplayOn,
+    Done,
+}
+
+/// Gamma correction curves for `set_gamma_preset`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GammaPreset {
+    /// Curves used by the Adafruit driver for the 1.8" red tab module.
+    RedTab18,
+    /// Curves from the Sitronix sample initialization code, used by many generic modules.
+    Sitronix,
+    /// Curves commonly used for 0.96" 80x160 ST7735S modules.
+    Mini096,
+}
+
+impl GammaPreset {
+    /// Returns the positive (`GMCTRP1`) and negative (`GMCTRN1`) polarity parameters.
+    fn tables(self) -> (&'static [u8; 16], &'static [u8; 16]) {
+        match self {
+            GammaPreset::RedTab18 => (
+                &[
+                    0x02, 0x1C, 0x07, 0x12, 0x37, 0x32, 0x29, 0x2D, 0x29, 0x25, 0x2B, 0x39, 0x00,
+                    0x01, 0x03, 0x10,
+                ],
+                &[
+                    0x03, 0x1D, 0x07, 0x06, 0x2E, 0x2C, 0x29, 0x2D, 0x2E, 0x2E, 0x37, 0x3F, 0x00,
+                    0x00, 0x02, 0x10,
+                ],
+            ),
+            GammaPreset::Sitronix => (
+                &[
+                    0x0F, 0x1A, 0x0F, 0x18, 0x2F, 0x28, 0x20, 0x22, 0x1F, 0x1B, 0x23, 0x37, 0x00,
+                    0x07, 0x02, 0x10,
+                ],
+                &[
+                    0x0F, 0x1B, 0x0F, 0x17, 0x33, 0x2C, 0x29, 0x2E, 0x30, 0x30, 0x39, 0x3F, 0x00,
+                    0x07, 0x03, 0x10,
+                ],
+            ),
+            GammaPreset::Mini096 => (
+                &[
+                    0x04, 0x22, 0x07, 0x0A, 0x2E, 0x30, 0x25, 0x2A, 0x28, 0x26, 0x2E, 0x3A, 0x00,
+                    0x01, 0x03, 0x13,
+                ],
+                &[
+                    0x04, 0x16, 0x06, 0x0D, 0x2D, 0x26, 0x23, 0x27, 0x27, 0x25, 0x2D, 0x3B, 0x00,
+                    0x01, 0x04, 0x13,
+                ],
+            ),
+        }
+    }
+}
+
+/// Byte order of the RGB565 colors passed to the driver.
+///
+/// The controller expects big-endian colors on the wire, so little-endian colors are swapped
+/// before they are sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Colors are regular RGB565 values, or big-endian bytes in byte buffers.
+    BigEndian,
+    /// Colors have their two bytes swapped, e.g. prepared for a little-endian DMA transfer.
+    LittleEndian,
+}
+
+/// Color depth of the pixel data sent to the display.
+///
+/// Colors are always passed to the driver as RGB565 and converted to the selected format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 12 bits per pixel, two pixels are packed into three bytes.
+    Rgb444 = 0x03,
+    /// 16 bits per pixel.
+    Rgb565 = 0x05,
+    /// 18 bits per pixel, sent as three bytes.
+    Rgb666 = 0x06,
+}
+
+impl PixelFormat {
+    /// Encodes a RGB565 color, returning the bytes to send and how many of them are used.
+    ///
+    /// `Rgb444` packs two pixels into three bytes, so the first pixel of a pair is kept in
+    /// `pending` and no bytes are returned for it.
+    fn encode(self, color: u16, pending: &mut Option<u16>) -> ([u8; 3], usize) {
+        match self {
+            PixelFormat::Rgb444 => match pending.take() {
+                None => {
+                    *pending = Some(color);
+                    ([0; 3], 0)
+                }
+                Some(first) => {
+                    let (r1, g1, b1) = rgb444(first);
+                    let (r2, g2, b2) = rgb444(color);
+                    ([r1 << 4 | g1, b1 << 4 | r2, g2 << 4 | b2], 3)
+                }
+            },
+            PixelFormat::Rgb565 => {
+                let bytes = color.to_be_bytes();
+                ([bytes[0], bytes[1], 0], 2)
+            }
+            PixelFormat::Rgb666 => {
+                let r = (color >> 11) as u8;
+                let g = (color >> 5) as u8 & 0x3F;
+                let b = color as u8 & 0x1F;
+                ([r << 3 | r >> 2, g << 2, b << 3 | b >> 2], 3)
+            }
+        }
+    }
+
+    /// Encodes the unpaired last pixel left in `pending` by `encode`.
+    fn encode_pending(pending: Option<u16>) -> ([u8; 3], usize) {
+        match pending {
+            Some(color) => {
+                let (r, g, b) = rgb444(color);
+                ([r << 4 | g, b << 4, 0], 2)
+            }
+            None => ([0; 3], 0),
+        }
+    }
+}
+
+/// Iterator adapter that reverses every row of `width` colors, for `set_mirror_x`.
+///
+/// A last row that ends early is reversed as far as it goes.
+struct MirroredRows<I> {
+    colors: I,
+    width: usize,
+    row: [u16; GRAM_ROWS as usize],
+    len: usize,
+}
+
+impl<I> MirroredRows<I> {
+    fn new(colors: I, width: u16) -> Self {
+        MirroredRows {
+            colors,
+            width: usize::from(width).clamp(1, GRAM_ROWS as usize),
+            row: [0; GRAM_ROWS as usize],
+            len: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u16>> Iterator for MirroredRows<I> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        if self.len == 0 {
+            for color in self.colors.by_ref().take(self.width) {
+                self.row[self.len] = color;
+                self.len += 1;
+            }
+        }
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.row[self.len])
+    }
+}
+
+/// Returns whether every byte of a read response is `0x00` or every byte is `0xFF`.
+fn is_floating(response: &[u8]) -> bool {
+    response.iter().all(|&byte| byte == 0x00) || response.iter().all(|&byte| byte == 0xFF)
+}
+
+/// Returns the inclusive window of a `width` x `height` block with its top left corner at
+/// (`sx`, `sy`). Returns an error if the block is empty or doesn't fit the coordinate range.
+fn window_of_size(sx: u16, sy: u16, width: u16, height: u16) -> Result<Window, ()> {
+    let ex = width.checked_sub(1).and_then(|w| sx.checked_add(w));
+    let ey = height.checked_sub(1).and_then(|h| sy.checked_add(h));
+    match (ex, ey) {
+        (Some(ex), Some(ey)) => Ok((sx, sy, ex, ey)),
+        _ => Err(()),
+    }
+}
+
+/// Splits a RGB565 color into 4-bit channels.
+fn rgb444(color: u16) -> (u8, u8, u8) {
+    (
+        (color >> 12) as u8,
+        (color >> 7) as u8 & 0x0F,
+        (color >> 1) as u8 & 0x0F,
+    )
 }
 
 impl<SPI, DC, RST> ST7735<SPI, DC, RST>
@@ -64,19 +481,73 @@ where
         width: u32,
         height: u32,
     ) -> Self {
-        let display = ST7735 {
+        Self::with_reset(spi, dc, Some(rst), rgb, inverted, width, height)
+    }
+
+    /// Creates a new driver instance from a stored configuration.
+    ///
+    /// The configured orientation is applied by `init`.
+    pub fn from_config(spi: SPI, dc: DC, rst: RST, config: DisplayConfig) -> Self {
+        let mut display = Self::new(
+            spi,
+            dc,
+            rst,
+            config.rgb,
+            config.inverted,
+            config.width,
+            config.height,
+        );
+        display.orientation = config.orientation;
+        display.set_offset(config.dx, config.dy);
+        display
+    }
+
+    fn with_reset(
+        spi: SPI,
+        dc: DC,
+        rst: Option<RST>,
+        rgb: bool,
+        inverted: bool,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        debug_assert!(
+            (width <= GRAM_COLUMNS && height <= GRAM_ROWS)
+                || (width <= GRAM_ROWS && height <= GRAM_COLUMNS),
+            "display size exceeds the ST7735 display RAM"
+        );
+        ST7735 {
             spi,
             dc,
             rst,
             rgb,
             inverted,
+            reset_active_low: true,
+            variant: Variant::ST7735,
+            pixel_format: PixelFormat::Rgb565,
+            byte_order: ByteOrder::BigEndian,
+            orientation: Orientation::Portrait,
+            line_order_reversed: false,
+            refresh_order_reversed: false,
+            read_dummy_bytes: 1,
+            init_settle_ms: 0,
+            mirror_x: false,
+            window_width: width as u16,
+            buffering_threshold: 16,
+            normal_mode_power: NORMAL_MODE_POWER,
+            initialized: false,
+            sleeping: true,
+            display_on: false,
+            #[cfg(feature = "stats")]
+            stats: Stats::default(),
+            orientation_offsets: [None; 4],
+            display_size: (width, height),
+            viewport_origin: (0, 0),
             dx: 0,
             dy: 0,
             width,
             height,
-        };
-
-        display
+        }
     }
 
     /// Runs commands to initialize the display.
@@ -84,51 +555,332 @@ where
     where
         DELAY: DelayMs<u8>,
     {
-        self.hard_reset(delay)?;
-        self.write_command(Instruction::SWRESET, &[])?;
-        delay.delay_ms(200);
-        self.write_command(Instruction::SLPOUT, &[])?;
-        delay.delay_ms(200);
-        self.write_command(Instruction::FRMCTR1, &[0x01, 0x2C, 0x2D])?;
-        self.write_command(Instruction::FRMCTR2, &[0x01, 0x2C, 0x2D])?;
-        self.write_command(Instruction::FRMCTR3, &[0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D])?;
-        self.write_command(Instruction::INVCTR, &[0x07])?;
-        self.write_command(Instruction::PWCTR1, &[0xA2, 0x02, 0x84])?;
-        self.write_command(Instruction::PWCTR2, &[0xC5])?;
-        self.write_command(Instruction::PWCTR3, &[0x0A, 0x00])?;
-        self.write_command(Instruction::PWCTR4, &[0x8A, 0x2A])?;
-        self.write_command(Instruction::PWCTR5, &[0x8A, 0xEE])?;
-        self.write_command(Instruction::VMCTR1, &[0x0E])?;
+        self.run_init(delay, DEFAULT_INIT, &[], true)
+    }
+
+    /// Runs commands to initialize the display with `sequence` in place of `DEFAULT_INIT`.
+    ///
+    /// The reset, sleep out, inversion, orientation, pixel format and display on commands
+    /// around it are sent as by `init`.
+    pub fn run_init_sequence<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        sequence: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.run_init(delay, sequence, &[], true)
+    }
+
+    /// Runs commands to initialize the display, sending `extra` right before the display is
+    /// turned on.
+    ///
+    /// This allows panel specific commands to be added without replacing the whole sequence.
+    pub fn init_with_extra<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        extra: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.run_init(delay, DEFAULT_INIT, extra, true)
+    }
+
+    /// Runs commands to initialize the display but leaves the display output off.
+    ///
+    /// The display RAM can then be drawn before `display_on` shows it, so the random contents
+    /// of the display RAM after reset are never visible.
+    pub fn init_display_off<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.run_init(delay, DEFAULT_INIT, &[], false)
+    }
+
+    /// Like `init_display_off`, sending `extra` at the end of the sequence.
+    pub fn init_display_off_with_extra<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        extra: &[InitStep],
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.run_init(delay, DEFAULT_INIT, extra, false)
+    }
+
+    /// Runs all init stages, waiting between them with a blocking delay.
+    fn run_init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        sequence: &[InitStep],
+        extra: &[InitStep],
+        display_on: bool,
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        let mut stage = InitStage::Reset(0);
+        while stage != InitStage::Done {
+            let (wait, next) = self.init_stage(stage, sequence, extra, display_on)?;
+            if wait > 0 {
+                delay.delay_ms(wait);
+            }
+            stage = next;
+        }
+        Ok(())
+    }
+
+    /// Runs a single stage of the init sequence. The blocking and async inits both step
+    /// through these stages, so they send the same commands.
+    ///
+    /// `extra` follows `sequence` and the configuration commands, and the display is turned on
+    /// at the end if `display_on` is set. Returns the time to wait before the next stage in
+    /// milliseconds, and that stage.
+    fn init_stage(
+        &mut self,
+        stage: InitStage,
+        sequence: &[InitStep],
+        extra: &[InitStep],
+        display_on: bool,
+    ) -> Result<(u8, InitStage), ()> {
+        Ok(match stage {
+            InitStage::Reset(_) if self.rst.is_none() => (0, InitStage::SoftwareReset),
+            InitStage::Reset(0) => {
+                self.set_reset(false)?;
+                (10, InitStage::Reset(1))
+            }
+            InitStage::Reset(1) => {
+                self.set_reset(true)?;
+                (10, InitStage::Reset(2))
+            }
+            InitStage::Reset(_) => {
+                self.set_reset(false)?;
+                (0, InitStage::SoftwareReset)
+            }
+            InitStage::SoftwareReset => {
+                self.write_command(Instruction::SWRESET, &[])?;
+                (200, InitStage::SleepOut)
+            }
+            InitStage::SleepOut => {
+                self.write_command(Instruction::SLPOUT, &[])?;
+                self.sleeping = false;
+                (200, InitStage::Sequence(0))
+            }
+            InitStage::Sequence(index) => match sequence.get(index) {
+                Some(step) => (self.run_init_step(step)?, InitStage::Sequence(index + 1)),
+                None => (0, InitStage::Config),
+            },
+            InitStage::Config => {
+                self.init_config()?;
+                (self.init_settle_ms, InitStage::Extra(0))
+            }
+            InitStage::Extra(index) => match extra.get(index) {
+                Some(step) => (self.run_init_step(step)?, InitStage::Extra(index + 1)),
+                None => {
+                    self.display_on = false;
+                    self.initialized = true;
+                    if display_on {
+                        (0, InitStage::DisplayOn)
+                    } else {
+                        (0, InitStage::Done)
+                    }
+                }
+            },
+            InitStage::DisplayOn => {
+                self.write_command(Instruction::DISPON, &[])?;
+                self.display_on = true;
+                (200, InitStage::Done)
+            }
+            InitStage::Done => (0, InitStage::Done),
+        })
+    }
+
+    /// Sends a step of an init sequence and returns how long to wait after it: the step's own
+    /// delay plus the init settle time.
+    fn run_init_step(&mut self, step: &InitStep) -> Result<u8, ()> {
+        self.write_command(step.cmd, step.args)?;
+        if let Instruction::PWCTR3 = step.cmd {
+            self.normal_mode_power = step.args;
+        }
+        Ok(step.delay_ms.saturating_add(self.init_settle_ms))
+    }
+
+    /// Sends the init commands that depend on the driver configuration: inversion, orientation
+    /// and pixel format.
+    fn init_config(&mut self) -> Result<(), ()> {
         if self.inverted {
             self.write_command(Instruction::INVON, &[])?;
         } else {
             self.write_command(Instruction::INVOFF, &[])?;
         }
-        if self.rgb {
-            self.write_command(Instruction::MADCTL, &[0x00])?;
-        } else {
-            self.write_command(Instruction::MADCTL, &[0x08])?;
-        }
-        self.write_command(Instruction::COLMOD, &[0x05])?;
+        let orientation = self.orientation;
+        self.set_orientation(&orientation)?;
+        self.write_command(Instruction::COLMOD, &[self.pixel_format as u8])?;
+        Ok(())
+    }
+
+    /// Puts the display into sleep mode.
+    pub fn sleep<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.write_command(Instruction::SLPIN, &[])?;
+        self.sleeping = true;
+        delay.delay_ms(120);
+        Ok(())
+    }
+
+    /// Wakes the display from sleep mode.
+    pub fn wake<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.write_command(Instruction::SLPOUT, &[])?;
+        self.sleeping = false;
+        delay.delay_ms(120);
+        Ok(())
+    }
+
+    /// Turns the display output on.
+    pub fn display_on(&mut self) -> Result<(), ()> {
         self.write_command(Instruction::DISPON, &[])?;
-        delay.delay_ms(200);
+        self.display_on = true;
         Ok(())
     }
 
+    /// Turns the display output off. The display RAM keeps its contents.
+    pub fn display_off(&mut self) -> Result<(), ()> {
+        self.write_command(Instruction::DISPOFF, &[])?;
+        self.display_on = false;
+        Ok(())
+    }
+
+    /// Stops (`true`) or restores (`false`) the operational amplifiers and booster circuits used
+    /// in normal mode.
+    ///
+    /// Unlike `sleep` the interface and display RAM stay active, so drawing can continue.
+    /// While enabled the panel is not driven and the image fades.
+    ///
+    /// The saving is the analog supply current of the booster and amplifiers, which the
+    /// datasheet doesn't list separately from the rest of the normal mode current, so measure
+    /// it on the target board. Restoring takes effect within a frame, about 17 ms at the frame
+    /// rate set by `DEFAULT_INIT`, while waking from `sleep` takes 120 ms.
+    ///
+    /// Restoring sends the `PWCTR3` parameters of the last init sequence, or those of
+    /// `DEFAULT_INIT` if the sequence didn't set `PWCTR3`.
+    pub fn power_save(&mut self, enable: bool) -> Result<(), ()> {
+        if enable {
+            self.write_command(Instruction::PWCTR3, &[0x00, 0x00])
+        } else {
+            self.write_command(Instruction::PWCTR3, self.normal_mode_power)
+        }
+    }
+
+    /// Turns the display output off and stops the booster circuits to reduce switching noise,
+    /// e.g. around sensitive ADC measurements.
+    ///
+    /// The display RAM keeps its contents, but the panel shows a brief blank until `unquiet` is
+    /// called.
+    pub fn quiet(&mut self) -> Result<(), ()> {
+        self.display_off()?;
+        self.power_save(true)
+    }
+
+    /// Restores the booster circuits and turns the display output back on after `quiet`.
+    pub fn unquiet(&mut self) -> Result<(), ()> {
+        self.power_save(false)?;
+        self.display_on()
+    }
+
+    /// Returns the bus traffic counters collected since the last call and resets them.
+    #[cfg(feature = "stats")]
+    pub fn take_stats(&mut self) -> Stats {
+        core::mem::take(&mut self.stats)
+    }
+
+    /// Returns the display to normal mode, leaving partial or scroll mode.
+    ///
+    /// The whole display area is shown again. The display RAM is not cleared.
+    pub fn normal_mode(&mut self) -> Result<(), ()> {
+        self.write_command(Instruction::NORON, &[])
+    }
+
+    /// Sends a `NOP` command and returns how long it took, measured with `now`.
+    ///
+    /// `now` returns the current value of a free-running timer, e.g. a cycle counter, and the
+    /// result is in its ticks. The difference is computed with wrapping arithmetic, so a single
+    /// timer overflow during the command is handled.
+    pub fn timed_nop<F: FnMut() -> u32>(&mut self, mut now: F) -> Result<u32, ()> {
+        let start = now();
+        self.write_command(Instruction::NOP, &[])?;
+        Ok(now().wrapping_sub(start))
+    }
+
+    /// Returns whether `init` has completed.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Returns whether the display is in sleep mode, as last set by this driver.
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Returns whether the display output is turned on, as last set by this driver.
+    pub fn is_on(&self) -> bool {
+        self.display_on
+    }
+
+    /// Pulses the reset pin. Does nothing if the driver has no reset pin.
     pub fn hard_reset<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
     where
         DELAY: DelayMs<u8>,
     {
-        self.rst.set_high().map_err(|_| ())?;
-        delay.delay_ms(10);
-        self.rst.set_low().map_err(|_| ())?;
-        delay.delay_ms(10);
-        self.rst.set_high().map_err(|_| ())
+        let mut stage = InitStage::Reset(0);
+        while let InitStage::Reset(_) = stage {
+            let (wait, next) = self.init_stage(stage, &[], &[], false)?;
+            if wait > 0 {
+                delay.delay_ms(wait);
+            }
+            stage = next;
+        }
+        Ok(())
+    }
+
+    /// Sets whether the reset pin is driven low (`true`, the default) or high to hold the
+    /// controller in reset.
+    ///
+    /// The controller's reset input is active low. Use `false` when the reset line is inverted
+    /// between the GPIO and the display, e.g. by a transistor.
+    pub fn set_reset_active_low(&mut self, active_low: bool) {
+        self.reset_active_low = active_low;
+    }
+
+    /// Drives the reset pin to hold the controller in reset (`true`) or release it, according to
+    /// the reset polarity. Does nothing if the driver has no reset pin.
+    fn set_reset(&mut self, asserted: bool) -> Result<(), ()> {
+        let rst = match self.rst.as_mut() {
+            Some(rst) => rst,
+            None => return Ok(()),
+        };
+        if asserted == self.reset_active_low {
+            rst.set_low().map_err(|_| ())
+        } else {
+            rst.set_high().map_err(|_| ())
+        }
     }
 
     fn write_command(&mut self, command: Instruction, params: &[u8]) -> Result<(), ()> {
         self.dc.set_low().map_err(|_| ())?;
         self.spi.write(&[command as u8]).map_err(|_| ())?;
+        #[cfg(feature = "stats")]
+        {
+            self.stats.commands = self.stats.commands.wrapping_add(1);
+            self.stats.bytes = self.stats.bytes.wrapping_add(1);
+        }
         if !params.is_empty() {
             self.start_data()?;
             self.write_data(params)?;
@@ -136,12 +888,42 @@ where
         Ok(())
     }
 
+    /// Sends a command with parameters taken from an iterator.
+    pub fn write_command_iter<I: IntoIterator<Item = u8>>(
+        &mut self,
+        command: Instruction,
+        params: I,
+    ) -> Result<(), ()> {
+        self.write_command(command, &[])?;
+        self.start_data()?;
+        self.write_bytes_buffered(params)
+    }
+
     fn start_data(&mut self) -> Result<(), ()> {
         self.dc.set_high().map_err(|_| ())
     }
 
     fn write_data(&mut self, data: &[u8]) -> Result<(), ()> {
-        self.spi.write(data).map_err(|_| ())
+        self.spi.write(data).map_err(|_| ())?;
+        #[cfg(feature = "stats")]
+        {
+            self.stats.bytes = self.stats.bytes.wrapping_add(data.len() as u32);
+        }
+        Ok(())
+    }
+
+    fn write_bytes_buffered(&mut self, bytes: impl IntoIterator<Item = u8>) -> Result<(), ()> {
+        let mut buffer = [0; 32];
+        let mut index = 0;
+        for byte in bytes {
+            if index == buffer.len() {
+                self.write_data(&buffer)?;
+                index = 0;
+            }
+            buffer[index] = byte;
+            index += 1;
+        }
+        self.write_data(&buffer[0..index])
     }
 
     /// Writes a data word to the display.
@@ -149,98 +931,1392 @@ where
         self.write_data(&value.to_be_bytes())
     }
 
-    fn write_words_buffered(&mut self, words: impl IntoIterator<Item = u16>) -> Result<(), ()> {
+    /// Encodes a color passed to the driver in the current byte order and pixel format.
+    fn encode_color(&self, color: u16, pending: &mut Option<u16>) -> ([u8; 3], usize) {
+        let color = match self.byte_order {
+            ByteOrder::BigEndian => color,
+            ByteOrder::LittleEndian => color.swap_bytes(),
+        };
+        self.pixel_format.encode(color, pending)
+    }
+
+    /// Writes a single color in the current pixel format.
+    fn write_color(&mut self, color: u16, pending: &mut Option<u16>) -> Result<(), ()> {
+        let (bytes, len) = self.encode_color(color, pending);
+        if len > 0 {
+            self.write_data(&bytes[0..len])?;
+        }
+        Ok(())
+    }
+
+    /// Writes colors into the current window, reversing every row when mirroring.
+    fn write_words_buffered(&mut self, words: impl IntoIterator<Item = u16>) -> Result<u32, ()> {
+        if self.mirror_x {
+            let rows = MirroredRows::new(words.into_iter(), self.window_width);
+            self.encode_words_buffered(rows)
+        } else {
+            self.encode_words_buffered(words)
+        }
+    }
+
+    fn encode_words_buffered(&mut self, words: impl IntoIterator<Item = u16>) -> Result<u32, ()> {
         let mut buffer = [0; 32];
         let mut index = 0;
+        let mut pending = None;
+        let mut count = 0;
         for word in words {
-            let as_bytes = word.to_be_bytes();
-            buffer[index] = as_bytes[0];
-            buffer[index + 1] = as_bytes[1];
-            index += 2;
-            if index >= buffer.len() {
-                self.write_data(&buffer)?;
+            count += 1;
+            let (bytes, len) = self.encode_color(word, &mut pending);
+            if index + len > buffer.len() {
+                self.write_data(&buffer[0..index])?;
                 index = 0;
             }
+            buffer[index..index + len].copy_from_slice(&bytes[0..len]);
+            index += len;
         }
-        self.write_data(&buffer[0..index])
+        let (bytes, len) = PixelFormat::encode_pending(pending);
+        buffer[index..index + len].copy_from_slice(&bytes[0..len]);
+        index += len;
+        self.write_data(&buffer[0..index])?;
+        Ok(count)
     }
 
-    pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), ()> {
-        if self.rgb {
-            self.write_command(Instruction::MADCTL, &[*orientation as u8])?;
-        } else {
-            self.write_command(Instruction::MADCTL, &[*orientation as u8 | 0x08])?;
+    /// Writes `count` pixels of the same color from a buffer pre-filled with the encoded color.
+    fn write_repeated(&mut self, color: u16, count: u32) -> Result<(), ()> {
+        // Encode two pixels so the pattern is a whole number of bytes in every pixel format.
+        let mut pending = None;
+        let mut pair = [0; 6];
+        let mut pair_len = 0;
+        for _ in 0..2 {
+            let (bytes, len) = self.encode_color(color, &mut pending);
+            pair[pair_len..pair_len + len].copy_from_slice(&bytes[0..len]);
+            pair_len += len;
+        }
+        let pair = &pair[0..pair_len];
+
+        // 48 bytes holds a whole number of 3, 4 and 6 byte pairs.
+        let mut buffer = [0; 48];
+        for chunk in buffer.chunks_mut(pair.len()) {
+            chunk.copy_from_slice(pair);
+        }
+        let pixels_per_buffer = (buffer.len() / pair.len() * 2) as u32;
+        for _ in 0..count / pixels_per_buffer {
+            self.write_data(&buffer)?;
+        }
+        let remainder = count % pixels_per_buffer;
+        if remainder >= 2 {
+            self.write_data(&buffer[0..(remainder / 2) as usize * pair.len()])?;
+        }
+        if remainder % 2 == 1 {
+            self.write_words_buffered(core::iter::once(color))?;
         }
         Ok(())
     }
 
-    /// Sets the global offset of the displayed image
-    pub fn set_offset(&mut self, dx: u16, dy: u16) {
-        self.dx = dx;
-        self.dy = dy;
+    pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), ()> {
+        self.set_madctl(
+            orientation,
+            self.line_order_reversed,
+            self.refresh_order_reversed,
+        )
     }
 
-    /// Sets the address window for the display.
-    fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), ()> {
-        self.write_command(Instruction::CASET, &[])?;
-        self.start_data()?;
-        self.write_word(sx + self.dx)?;
-        self.write_word(ex + self.dx)?;
+    /// Sets the orientation together with the panel refresh order bits of `MADCTL`.
+    ///
+    /// `line_order_reversed` sets ML, which refreshes the panel from the bottom line to the top.
+    /// `refresh_order_reversed` sets MH, which refreshes each line from right to left. Neither
+    /// changes where pixels are drawn, only the order in which the panel scans them out, which
+    /// can hide tearing on panels mounted upside down. The bits are kept by `set_orientation`.
+    pub fn set_madctl(
+        &mut self,
+        orientation: &Orientation,
+        line_order_reversed: bool,
+        refresh_order_reversed: bool,
+    ) -> Result<(), ()> {
+        let mut madctl = *orientation as u8;
+        if !self.rgb {
+            madctl |= MADCTL_BGR;
+        }
+        if line_order_reversed {
+            madctl |= MADCTL_ML;
+        }
+        if refresh_order_reversed {
+            madctl |= MADCTL_MH;
+        }
+        self.write_command(Instruction::MADCTL, &[madctl])?;
+        self.orientation = *orientation;
+        if let Some((dx, dy)) = self.orientation_offsets[orientation.index()] {
+            self.set_offset(dx, dy);
+        }
+        self.line_order_reversed = line_order_reversed;
+        self.refresh_order_reversed = refresh_order_reversed;
+        Ok(())
+    }
+
+    /// Sets the number of dummy bytes discarded before pixel data when reading the display RAM,
+    /// and before the response when reading the display ID.
+    ///
+    /// Defaults to one byte, which is what most ST7735 variants send.
+    pub fn set_read_dummy_bytes(&mut self, count: u8) {
+        self.read_dummy_bytes = count;
+    }
+
+    /// Sets the color depth of the pixel data sent to the display.
+    pub fn set_pixel_format(&mut self, pixel_format: PixelFormat) -> Result<(), ()> {
+        self.write_command(Instruction::COLMOD, &[pixel_format as u8])?;
+        self.pixel_format = pixel_format;
+        Ok(())
+    }
+
+    /// Returns the color depth of the pixel data sent to the display, as last set by
+    /// `set_pixel_format`.
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    /// Sets the byte order of all colors and pixel data passed to the driver.
+    ///
+    /// Defaults to `ByteOrder::BigEndian`, which matches what the controller expects.
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order;
+    }
+
+    /// Sets an extra delay in milliseconds that `init` waits after every step of the sequence,
+    /// on top of the step's own delay. Defaults to 0.
+    ///
+    /// Some cheap panels glitch unless they get time to settle between init commands. Only the
+    /// init sequence is covered: the driver is given a delay for `init`, `sleep` and `wake` but
+    /// doesn't keep one, so commands sent while drawing (`CASET`, `RASET`, `RAMWR`, `MADCTL`,
+    /// ...) are sent back to back. Lower the SPI clock if those glitch as well.
+    pub fn set_init_settle_ms(&mut self, ms: u8) {
+        self.init_settle_ms = ms;
+    }
+
+    /// Mirrors all drawing horizontally in software, so logical column `x` is drawn at
+    /// `width - 1 - x`, e.g. for a display reflected off glass.
+    ///
+    /// Drawing windows are moved to their mirrored position and every row of streamed pixels is
+    /// written in reverse, so images and text come out mirrored the same way as lines and fills,
+    /// whatever text style is used. Reversing a row needs it buffered, which takes a copy of up
+    /// to 162 colors on the stack per write. `read_pixels` reverses the rows again and returns
+    /// the pixels in logical order.
+    ///
+    /// Mirroring in hardware with the `MADCTL` MX bit gives the same image without the copy,
+    /// but mirrors across the whole display RAM, so the image offset usually has to change.
+    /// Enabling both cancels out.
+    pub fn set_mirror_x(&mut self, mirror: bool) {
+        self.mirror_x = mirror;
+    }
+
+    /// Sets the window size in pixels from which `set_pixels` buffers its writes. Defaults to
+    /// 16. Use 0 to always buffer and `u32::MAX` to never buffer.
+    pub fn set_buffering_threshold(&mut self, pixels: u32) {
+        self.buffering_threshold = pixels;
+    }
+
+    /// Sets the controller variant. Defaults to `Variant::ST7735`.
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+
+    /// Returns the size of the display RAM of the selected controller variant as
+    /// `(columns, rows)` in the default portrait orientation.
+    ///
+    /// The configured width and height plus offsets must fit within these bounds, with width and
+    /// height swapped in landscape orientations.
+    pub fn max_resolution(&self) -> (u16, u16) {
+        match self.variant {
+            Variant::ST7735 | Variant::ST7735S => (GRAM_COLUMNS as u16, GRAM_ROWS as u16),
+        }
+    }
+
+    /// Sets the VCOM offset (`VMOFCTR`). Only supported by the ST7735S, returns an error for
+    /// other variants without sending anything.
+    pub fn set_vcom_offset(&mut self, offset: u8) -> Result<(), ()> {
+        if self.variant != Variant::ST7735S {
+            return Err(());
+        }
+        self.write_command(Instruction::VMOFCTR, &[offset & 0x1F])
+    }
+
+    /// Applies the positive and negative polarity gamma curves of a preset.
+    pub fn set_gamma_preset(&mut self, preset: GammaPreset) -> Result<(), ()> {
+        let (positive, negative) = preset.tables();
+        self.write_command(Instruction::GMCTRP1, positive)?;
+        self.write_command(Instruction::GMCTRN1, negative)
+    }
+
+    /// Sets the global offset of the displayed image
+    ///
+    /// Any offset is allowed as long as the image stays within the 132x162 display RAM, so
+    /// panels that use the whole display RAM work with a zero offset. The offset is checked
+    /// when drawing: windows that the offset moves past the display RAM in the current
+    /// orientation return an error without being sent.
+    pub fn set_offset(&mut self, dx: u16, dy: u16) {
+        self.dx = dx;
+        self.dy = dy;
+    }
+
+    /// Runs `f` with the image offset moved by (`dx`, `dy`) relative to the current one, e.g. to
+    /// draw a shadow below and to the right of an element.
+    ///
+    /// The previous offset is restored afterwards, also when `f` returns an error. Returns an
+    /// error without calling `f` if the shifted offset overflows.
+    pub fn with_offset<T, F>(&mut self, dx: u16, dy: u16, f: F) -> Result<T, ()>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ()>,
+    {
+        let (old_dx, old_dy) = (self.dx, self.dy);
+        self.dx = old_dx.checked_add(dx).ok_or(())?;
+        self.dy = match old_dy.checked_add(dy) {
+            Some(dy) => dy,
+            None => {
+                self.dx = old_dx;
+                return Err(());
+            }
+        };
+        let result = f(self);
+        self.dx = old_dx;
+        self.dy = old_dy;
+        result
+    }
+
+    /// Restricts all drawing to the `(sx, sy, ex, ey)` window of the display, which then acts
+    /// as a smaller display with its origin at the window's top left corner.
+    ///
+    /// Drawing coordinates are moved into the viewport. Helpers that clip to the display,
+    /// including the embedded-graphics `DrawTarget`, clip to the viewport instead, and every
+    /// other drawing window that doesn't lie within the viewport returns an error without being
+    /// sent. `fill_screen` and `clear` only fill the viewport. Returns an error if the window
+    /// doesn't lie within the display.
+    pub fn set_viewport(&mut self, area: Window) -> Result<(), ()> {
+        let (sx, sy, ex, ey) = area;
+        if ex < sx
+            || ey < sy
+            || u32::from(ex) >= self.display_size.0
+            || u32::from(ey) >= self.display_size.1
+        {
+            return Err(());
+        }
+        self.viewport_origin = (sx, sy);
+        self.width = u32::from(ex - sx) + 1;
+        self.height = u32::from(ey - sy) + 1;
+        Ok(())
+    }
+
+    /// Removes the viewport set by `set_viewport`, making the whole display drawable again.
+    pub fn clear_viewport(&mut self) {
+        self.viewport_origin = (0, 0);
+        self.width = self.display_size.0;
+        self.height = self.display_size.1;
+    }
+
+    /// Sets the image offset to use in `orientation`, replacing the global offset whenever that
+    /// orientation is selected.
+    ///
+    /// Panels that don't fill the whole display RAM often need a different offset once rotated.
+    /// The offset is applied right away if `orientation` is the current one.
+    pub fn set_offset_for(&mut self, orientation: Orientation, dx: u16, dy: u16) {
+        self.orientation_offsets[orientation.index()] = Some((dx, dy));
+        if orientation == self.orientation {
+            self.set_offset(dx, dy);
+        }
+    }
+
+    /// Sets the address window for the display.
+    ///
+    /// In debug builds this returns an error if the display hasn't been initialized, which
+    /// makes drawing before `init` fail instead of silently showing nothing. Swapped coordinates
+    /// (`ex < sx` or `ey < sy`) and windows that don't lie within the display or viewport also
+    /// return an error without sending anything.
+    fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), ()> {
+        if ex < sx || ey < sy || u32::from(ex) >= self.width || u32::from(ey) >= self.height {
+            return Err(());
+        }
+        self.set_column_window(sx, ex)?;
+        self.set_row_window(sy, ey)
+    }
+
+    /// Sets the drawing window to the whole display, from (0, 0) to (width - 1, height - 1)
+    /// inclusive. The image offset is applied like for every other window.
+    pub fn reset_window(&mut self) -> Result<(), ()> {
+        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)
+    }
+
+    /// Sets the column range of the drawing window (`CASET`), applying the horizontal offset.
+    ///
+    /// The row range is kept, so several row ranges can be written with `set_row_window` and
+    /// `write_pixels` without resending the columns. Returns an error if `ex < sx` or if the
+    /// range doesn't lie within the display width (the viewport, if one is set).
+    ///
+    /// With `set_mirror_x` enabled the range is mirrored. Returns an error if the offset moves
+    /// the range past the display RAM in the current orientation.
+    pub fn set_column_window(&mut self, sx: u16, ex: u16) -> Result<(), ()> {
+        if (cfg!(debug_assertions) && !self.initialized) || ex < sx || u32::from(ex) >= self.width {
+            return Err(());
+        }
+        let (sx, ex) = if self.mirror_x {
+            let last = self.width as u16 - 1;
+            (last - ex, last - sx)
+        } else {
+            (sx, ex)
+        };
+        let x = u32::from(self.viewport_origin.0) + u32::from(self.dx);
+        if u32::from(ex) + x >= self.gram_size().0 {
+            return Err(());
+        }
+        self.write_command(Instruction::CASET, &[])?;
+        self.start_data()?;
+        self.write_word((u32::from(sx) + x) as u16)?;
+        self.write_word((u32::from(ex) + x) as u16)?;
+        self.window_width = ex - sx + 1;
+        Ok(())
+    }
+
+    /// Sets the row range of the drawing window (`RASET`), applying the vertical offset.
+    ///
+    /// Returns an error if `ey < sy`, if the range doesn't lie within the display height (the
+    /// viewport, if one is set), or if the offset moves the range past the display RAM in the
+    /// current orientation.
+    pub fn set_row_window(&mut self, sy: u16, ey: u16) -> Result<(), ()> {
+        if (cfg!(debug_assertions) && !self.initialized) || ey < sy || u32::from(ey) >= self.height
+        {
+            return Err(());
+        }
+        let y = u32::from(self.viewport_origin.1) + u32::from(self.dy);
+        if u32::from(ey) + y >= self.gram_size().1 {
+            return Err(());
+        }
         self.write_command(Instruction::RASET, &[])?;
         self.start_data()?;
-        self.write_word(sy + self.dy)?;
-        self.write_word(ey + self.dy)
+        self.write_word((u32::from(sy) + y) as u16)?;
+        self.write_word((u32::from(ey) + y) as u16)
+    }
+
+    /// Returns the size of the display RAM as `(columns, rows)` in the current orientation.
+    fn gram_size(&self) -> (u32, u32) {
+        let (columns, rows) = self.max_resolution();
+        if self.orientation as u8 & MADCTL_MV != 0 {
+            (rows.into(), columns.into())
+        } else {
+            (columns.into(), rows.into())
+        }
+    }
+
+    /// Sets a pixel color at the given coords.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), ()> {
+        self.set_address_window(x, y, x, y)?;
+        self.write_pixels(core::iter::once(color))
+    }
+
+    /// Writes pixel colors sequentially into the current drawing window
+    ///
+    /// Pixels are written in row-major order: the controller starts at the top left corner of
+    /// the window and continues at the start of the next row of the window when it passes the
+    /// right edge. The same holds for every other method that writes into a window.
+    pub fn write_pixels<P: IntoIterator<Item = u16>>(&mut self, colors: P) -> Result<(), ()> {
+        if self.mirror_x {
+            // Rows have to be buffered to reverse them.
+            return self.write_pixels_buffered(colors).map(|_| ());
+        }
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        let mut pending = None;
+        for color in colors {
+            self.write_color(color, &mut pending)?;
+        }
+        let (bytes, len) = PixelFormat::encode_pending(pending);
+        self.write_data(&bytes[0..len])
+    }
+
+    /// Writes pixel colors to the current address window, buffering them into larger SPI writes.
+    ///
+    /// Returns the number of pixels written.
+    pub fn write_pixels_buffered<P: IntoIterator<Item = u16>>(
+        &mut self,
+        colors: P,
+    ) -> Result<u32, ()> {
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        self.write_words_buffered(colors)
+    }
+
+    /// Writes a slice of pixel colors into the current drawing window.
+    ///
+    /// In RGB565 mode chunks of the slice are converted to bytes in a tight loop and written
+    /// from a small buffer, which avoids the per-pixel overhead of the iterator based writers.
+    /// Other pixel formats, and mirrored drawing, are written like `write_pixels_buffered`.
+    pub fn write_pixels_slice(&mut self, colors: &[u16]) -> Result<(), ()> {
+        if self.pixel_format != PixelFormat::Rgb565 || self.mirror_x {
+            self.write_pixels_buffered(colors.iter().copied())?;
+            return Ok(());
+        }
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        let mut buffer = [0; 64];
+        for chunk in colors.chunks(buffer.len() / 2) {
+            let bytes = &mut buffer[0..chunk.len() * 2];
+            for (pair, color) in bytes.chunks_exact_mut(2).zip(chunk) {
+                pair.copy_from_slice(&match self.byte_order {
+                    ByteOrder::BigEndian => color.to_be_bytes(),
+                    ByteOrder::LittleEndian => color.to_le_bytes(),
+                });
+            }
+            self.write_data(bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Continues a pixel write at the controller's current position, without sending `RAMWR`.
+    ///
+    /// A write must already have been started with one of the `write_pixels` or `set_pixels`
+    /// methods, and no other command may have been sent since, as any command ends the write.
+    /// In RGB444 mode pixels are packed in pairs, so every call must pass an even number of
+    /// pixels for the data to stay aligned. With `set_mirror_x` enabled rows are reversed per
+    /// call, so every call must continue at the start of a row. Returns the number of pixels
+    /// written.
+    pub fn append_pixels<P: IntoIterator<Item = u16>>(&mut self, colors: P) -> Result<u32, ()> {
+        self.start_data()?;
+        self.write_words_buffered(colors)
+    }
+
+    /// Sets pixel colors row by row, starting at the given coords.
+    ///
+    /// The window width is taken from the first row and the height from the number of rows.
+    /// Every row is written into its own one row window after checking its length, so a row
+    /// with a different length than the first one returns an error before any of its pixels are
+    /// sent. The rows above it are drawn, that row and the rows below it are left unchanged.
+    pub fn set_pixels_rows<R, C>(&mut self, sx: u16, sy: u16, rows: R) -> Result<(), ()>
+    where
+        R: IntoIterator<Item = C>,
+        R::IntoIter: ExactSizeIterator,
+        C: IntoIterator<Item = u16>,
+        C::IntoIter: ExactSizeIterator,
+    {
+        let mut rows = rows.into_iter();
+        let height = u16::try_from(rows.len()).map_err(|_| ())?;
+        let first = match rows.next() {
+            Some(row) => row.into_iter(),
+            None => return Ok(()),
+        };
+        let width = first.len();
+        if width == 0 {
+            return Ok(());
+        }
+
+        let (sx, sy, ex, ey) =
+            window_of_size(sx, sy, u16::try_from(width).map_err(|_| ())?, height)?;
+        self.set_column_window(sx, ex)?;
+        let rows = core::iter::once(first).chain(rows.map(IntoIterator::into_iter));
+        for (y, row) in (sy..=ey).zip(rows) {
+            if row.len() != width {
+                return Err(());
+            }
+            self.set_row_window(y, y)?;
+            self.write_pixels_buffered(row)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `count` pixels starting at the given coords, continuing at the start of the next
+    /// row when the right edge of the display is reached.
+    ///
+    /// Returns an error without drawing anything if the pixels would run past the bottom of the
+    /// display, and an error after drawing them if `colors` yields fewer than `count` pixels.
+    pub fn fill_n<P: IntoIterator<Item = u16>>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        count: u32,
+        colors: P,
+    ) -> Result<(), ()> {
+        if count == 0 {
+            return Ok(());
+        }
+        if u32::from(sx) >= self.width {
+            return Err(());
+        }
+        let mut colors = colors.into_iter();
+
+        let first_row = count.min(self.width - u32::from(sx));
+        let remaining = count - first_row;
+        let rows = remaining.div_ceil(self.width);
+        if u32::from(sy) + 1 + rows > self.height {
+            return Err(());
+        }
+        self.set_address_window(sx, sy, sx + first_row as u16 - 1, sy)?;
+        let mut written = self.write_pixels_buffered(colors.by_ref().take(first_row as usize))?;
+
+        if remaining > 0 && written == first_row {
+            self.set_address_window(0, sy + 1, self.width as u16 - 1, sy + rows as u16)?;
+            written += self.write_pixels_buffered(colors.take(remaining as usize))?;
+        }
+
+        if written == count {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Writes a full-height column of pixels at `x`, from top to bottom.
+    ///
+    /// Returns an error if `colors` doesn't yield exactly one color per display row.
+    pub fn write_column<P: IntoIterator<Item = u16>>(
+        &mut self,
+        x: u16,
+        colors: P,
+    ) -> Result<(), ()> {
+        let mut colors = colors.into_iter();
+        self.set_address_window(x, 0, x, self.height as u16 - 1)?;
+        let written = self.write_pixels_buffered(colors.by_ref().take(self.height as usize))?;
+        if written == self.height && colors.next().is_none() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Draws the part of `content` that is visible when it is scrolled left by `offset` columns.
+    ///
+    /// `content` holds rows of `content_width` colors and may be wider than the display. Columns
+    /// past its right edge wrap around to the start, so increasing `offset` scrolls it in a
+    /// seamless loop. Rows below the display are skipped.
+    pub fn scroll_horizontal(
+        &mut self,
+        content: &[u16],
+        content_width: u16,
+        offset: u16,
+    ) -> Result<(), ()> {
+        if content_width == 0 || !content.len().is_multiple_of(usize::from(content_width)) {
+            return Err(());
+        }
+        let content_width = usize::from(content_width);
+        let rows = (content.len() / content_width).min(self.height as usize);
+        if rows == 0 {
+            return Ok(());
+        }
+        let width = self.width as usize;
+        let offset = usize::from(offset) % content_width;
+        self.set_address_window(0, 0, width as u16 - 1, rows as u16 - 1)?;
+        self.write_pixels_buffered(
+            content
+                .chunks(content_width)
+                .take(rows)
+                .flat_map(|row| (0..width).map(move |x| row[(offset + x) % content_width])),
+        )?;
+        Ok(())
+    }
+
+    /// Draws a `width` x `height` block of colors, given row by row, with its top left corner at
+    /// the given coords, e.g. a sprite cell of a tile engine.
+    ///
+    /// Returns an error unless `colors` holds exactly `width * height` colors.
+    pub fn draw_cell(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        width: u16,
+        height: u16,
+        colors: &[u16],
+    ) -> Result<(), ()> {
+        if colors.len() != usize::from(width) * usize::from(height) {
+            return Err(());
+        }
+        if colors.is_empty() {
+            return Ok(());
+        }
+        let (sx, sy, ex, ey) = window_of_size(sx, sy, width, height)?;
+        self.set_pixels_slice(sx, sy, ex, ey, colors)
+    }
+
+    /// Draws a `width` x `height` 1 bit per pixel image at the given coords, scaled up so every
+    /// pixel becomes a `scale` x `scale` block, e.g. for QR codes.
+    ///
+    /// Each row starts at a new byte and pixels are packed most significant bit first. Set bits
+    /// are drawn in `fg`, clear bits in `bg`. The scaled image is streamed into a single window.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_scaled_1bpp(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+        scale: u16,
+        fg: u16,
+        bg: u16,
+    ) -> Result<(), ()> {
+        let stride = usize::from(width).div_ceil(8);
+        if data.len() != stride * usize::from(height) || scale == 0 {
+            return Err(());
+        }
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let scaled_width = width.checked_mul(scale).ok_or(())?;
+        let scaled_height = height.checked_mul(scale).ok_or(())?;
+        let (sx, sy, ex, ey) = window_of_size(sx, sy, scaled_width, scaled_height)?;
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_pixels_buffered(
+            data.chunks(stride)
+                .flat_map(|row| core::iter::repeat_n(row, usize::from(scale)))
+                .flat_map(|row| {
+                    (0..usize::from(width)).flat_map(move |x| {
+                        let color = if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                            fg
+                        } else {
+                            bg
+                        };
+                        core::iter::repeat_n(color, usize::from(scale))
+                    })
+                }),
+        )?;
+        Ok(())
+    }
+
+    /// Draws a `width` x `height` image of 4-bit palette indices at the given coords.
+    ///
+    /// Every byte holds two pixels, the high nibble first, and each row starts at a new byte.
+    /// The indices are looked up in `palette` and streamed into a single window.
+    pub fn draw_paletted_4bpp(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        width: u16,
+        height: u16,
+        indices: &[u8],
+        palette: &[u16; 16],
+    ) -> Result<(), ()> {
+        let stride = usize::from(width).div_ceil(2);
+        if indices.len() != stride * usize::from(height) {
+            return Err(());
+        }
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let (sx, sy, ex, ey) = window_of_size(sx, sy, width, height)?;
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_pixels_buffered(indices.chunks(stride).flat_map(|row| {
+            row.iter()
+                .flat_map(|&byte| {
+                    [
+                        palette[usize::from(byte >> 4)],
+                        palette[usize::from(byte & 0x0F)],
+                    ]
+                })
+                .take(usize::from(width))
+        }))?;
+        Ok(())
+    }
+
+    /// Draws a `width` x `height` block of colors at the given coords, skipping every pixel that
+    /// matches `transparent`.
+    ///
+    /// Each row is split into runs of opaque pixels and every run is written to its own window.
+    pub fn blit_region_keyed(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        width: u16,
+        height: u16,
+        colors: &[u16],
+        transparent: u16,
+    ) -> Result<(), ()> {
+        if colors.len() != usize::from(width) * usize::from(height) {
+            return Err(());
+        }
+        if colors.is_empty() {
+            return Ok(());
+        }
+        let (sx, sy, _, ey) = window_of_size(sx, sy, width, height)?;
+        for (y, row) in (sy..=ey).zip(colors.chunks(usize::from(width))) {
+            let mut x = 0;
+            while x < row.len() {
+                if row[x] == transparent {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < row.len() && row[x] != transparent {
+                    x += 1;
+                }
+                let (run_sx, _, run_ex, _) = window_of_size(
+                    sx.checked_add(start as u16).ok_or(())?,
+                    y,
+                    (x - start) as u16,
+                    1,
+                )?;
+                self.set_pixels_buffered(run_sx, y, run_ex, y, row[start..x].iter().copied())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets pixel colors at the given drawing window from big-endian RGB565 bytes.
+    fn set_pixels_raw(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        data: &[u8],
+    ) -> Result<(), ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        if self.pixel_format == PixelFormat::Rgb565
+            && self.byte_order == ByteOrder::BigEndian
+            && !self.mirror_x
+        {
+            self.write_data(data)
+        } else {
+            self.write_words_buffered(
+                data.chunks(2)
+                    .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]])),
+            )
+            .map(|_| ())
+        }
+    }
+
+    /// Writes a full-screen framebuffer of big-endian RGB565 bytes in a single pass.
+    ///
+    /// Returns an error if `data` is not exactly `width * height * 2` bytes long.
+    pub fn flush_framebuffer(&mut self, data: &[u8]) -> Result<(), ()> {
+        if data.len() != (self.width * self.height * 2) as usize {
+            return Err(());
+        }
+        self.set_pixels_raw(0, 0, self.width as u16 - 1, self.height as u16 - 1, data)
+    }
+
+    /// Fills the given drawing window with a single color.
+    pub fn fill_window(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        color: u16,
+    ) -> Result<(), ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        self.write_repeated(color, (u32::from(ex - sx) + 1) * (u32::from(ey - sy) + 1))
+    }
+
+    /// Fills the given drawing window with a checkerboard of `cell` x `cell` squares, starting
+    /// with `color_a` in the top left corner.
+    ///
+    /// Squares at the right and bottom edges are cut off if the window size isn't a multiple of
+    /// `cell`. The whole pattern is streamed into a single window.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_checkerboard(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        cell: u16,
+        color_a: u16,
+        color_b: u16,
+    ) -> Result<(), ()> {
+        if cell == 0 {
+            return Err(());
+        }
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_pixels_buffered((0..=ey - sy).flat_map(|y| {
+            (0..=ex - sx).map(move |x| {
+                if (x / cell + y / cell).is_multiple_of(2) {
+                    color_a
+                } else {
+                    color_b
+                }
+            })
+        }))?;
+        Ok(())
+    }
+
+    /// Fills every `((sx, sy, ex, ey), color)` window in `rects`, e.g. a list of dirty
+    /// rectangles.
+    ///
+    /// The column range is only resent when it differs from the previous rectangle, so
+    /// rectangles stacked in the same columns only cost a row window each.
+    pub fn fill_rects(&mut self, rects: &[(Window, u16)]) -> Result<(), ()> {
+        let mut columns = None;
+        for &((sx, sy, ex, ey), color) in rects {
+            if columns != Some((sx, ex)) {
+                self.set_column_window(sx, ex)?;
+                columns = Some((sx, ex));
+            }
+            self.set_row_window(sy, ey)?;
+            self.write_command(Instruction::RAMWR, &[])?;
+            self.start_data()?;
+            self.write_repeated(color, (u32::from(ex - sx) + 1) * (u32::from(ey - sy) + 1))?;
+        }
+        Ok(())
+    }
+
+    /// Draws a rectangle covering the `(sx, sy, ex, ey)` window, with an optional fill and an
+    /// optional outline of `stroke_width` pixels drawn inside the window.
+    ///
+    /// The outline is drawn as four bands and the fill as a single window, so a rectangle takes
+    /// at most five window fills. A stroke wider than half the rectangle covers it completely.
+    pub fn draw_rect(
+        &mut self,
+        area: Window,
+        fill: Option<u16>,
+        stroke: Option<u16>,
+        stroke_width: u16,
+    ) -> Result<(), ()> {
+        let (sx, sy, ex, ey) = area;
+        if ex < sx || ey < sy {
+            return Err(());
+        }
+        let stroke = match stroke {
+            Some(color) if stroke_width > 0 => color,
+            _ => {
+                return match fill {
+                    Some(color) => self.fill_window(sx, sy, ex, ey, color),
+                    None => Ok(()),
+                }
+            }
+        };
+        if u32::from(stroke_width) * 2 > u32::from(ex - sx)
+            || u32::from(stroke_width) * 2 > u32::from(ey - sy)
+        {
+            return self.fill_window(sx, sy, ex, ey, stroke);
+        }
+
+        let w = stroke_width;
+        self.fill_window(sx, sy, ex, sy + w - 1, stroke)?;
+        self.fill_window(sx, ey - w + 1, ex, ey, stroke)?;
+        self.fill_window(sx, sy + w, sx + w - 1, ey - w, stroke)?;
+        self.fill_window(ex - w + 1, sy + w, ex, ey - w, stroke)?;
+        if let Some(color) = fill {
+            self.fill_window(sx + w, sy + w, ex - w, ey - w, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a vertical bar `width` pixels wide at column `x`, e.g. for a level meter.
+    ///
+    /// Rows from `value_top` down to `baseline` are filled with `fill`, rows above `value_top`
+    /// with `empty`, so a bar takes at most two window fills. If `value_top` is below
+    /// `baseline` the whole bar is empty. Returns an error if the bar doesn't fit the coordinate
+    /// range.
+    pub fn draw_bar(
+        &mut self,
+        x: u16,
+        width: u16,
+        baseline: u16,
+        value_top: u16,
+        fill: u16,
+        empty: u16,
+    ) -> Result<(), ()> {
+        if width == 0 {
+            return Ok(());
+        }
+        let ex = x.checked_add(width - 1).ok_or(())?;
+        let value_top = value_top.min(baseline.checked_add(1).ok_or(())?);
+        if value_top > 0 {
+            self.fill_window(x, 0, ex, value_top - 1, empty)?;
+        }
+        if value_top <= baseline {
+            self.fill_window(x, value_top, ex, baseline, fill)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a full-width beveled separator: a `light` line at row `y` above a `dark` line at
+    /// row `y + 1`. The dark line is left out on the last row of the display.
+    pub fn draw_bevel_separator(&mut self, y: u16, light: u16, dark: u16) -> Result<(), ()> {
+        let ex = self.width as u16 - 1;
+        self.fill_window(0, y, ex, y, light)?;
+        if u32::from(y) + 1 < self.height {
+            self.fill_window(0, y + 1, ex, y + 1, dark)?;
+        }
+        Ok(())
+    }
+
+    /// Fills the whole display with a single color.
+    ///
+    /// The window and `RAMWR` are sent once, followed by chunks of a buffer pre-filled with the
+    /// encoded color.
+    pub fn fill_screen(&mut self, color: u16) -> Result<(), ()> {
+        self.fill_window(0, 0, self.width as u16 - 1, self.height as u16 - 1, color)
+    }
+
+    /// Sets pixel colors at the given drawing window
+    ///
+    /// Windows of at least the buffering threshold (see `set_buffering_threshold`) are written
+    /// like `set_pixels_buffered`, smaller ones one pixel per SPI write.
+    pub fn set_pixels<P: IntoIterator<Item = u16>>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: P,
+    ) -> Result<(), ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        let size = (u32::from(ex - sx) + 1) * (u32::from(ey - sy) + 1);
+        if size >= self.buffering_threshold {
+            self.write_pixels_buffered(colors)?;
+            Ok(())
+        } else {
+            self.write_pixels(colors)
+        }
+    }
+
+    /// Sets pixel colors at the given drawing window from a slice, see `write_pixels_slice`.
+    pub fn set_pixels_slice(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: &[u16],
+    ) -> Result<(), ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_pixels_slice(colors)
+    }
+
+    /// Sets pixel colors in the given window, buffering them into larger SPI writes.
+    ///
+    /// Returns the number of pixels written, which is less than the window size if `colors` runs
+    /// out early.
+    pub fn set_pixels_buffered<P: IntoIterator<Item = u16>>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: P,
+    ) -> Result<u32, ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_pixels_buffered(colors)
+    }
+
+    /// Fades a full-screen framebuffer to black in `steps` steps, waiting `step_ms` between them.
+    ///
+    /// The display has no brightness control in RGB565 mode, so every step scales the colors of
+    /// `framebuffer` towards black and writes the whole screen again.
+    pub fn fade_out<DELAY>(
+        &mut self,
+        framebuffer: &[u16],
+        steps: u8,
+        step_ms: u8,
+        delay: &mut DELAY,
+    ) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        if framebuffer.len() != (self.width * self.height) as usize {
+            return Err(());
+        }
+        for step in 1..=u16::from(steps) {
+            let factor = (255 * (u16::from(steps) - step) / u16::from(steps)) as u8;
+            self.set_pixels_buffered(
+                0,
+                0,
+                self.width as u16 - 1,
+                self.height as u16 - 1,
+                framebuffer
+                    .iter()
+                    .map(|&color| color::scale565(color, factor)),
+            )?;
+            delay.delay_ms(step_ms);
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8> + spi::Transfer<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    fn read_data(&mut self, data: &mut [u8]) -> Result<(), ()> {
+        data.iter_mut().for_each(|byte| *byte = 0);
+        self.spi.transfer(data).map(|_| ()).map_err(|_| ())
+    }
+
+    /// Reads and discards the dummy bytes sent before the response of a multi-byte read.
+    fn skip_dummy_bytes(&mut self) -> Result<(), ()> {
+        let mut dummy = [0];
+        for _ in 0..self.read_dummy_bytes {
+            self.read_data(&mut dummy)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a read command and returns its single byte response.
+    fn read_register(&mut self, command: Instruction) -> Result<u8, ()> {
+        self.write_command(command, &[])?;
+        self.start_data()?;
+        let mut data = [0];
+        self.read_data(&mut data)?;
+        Ok(data[0])
+    }
+
+    /// Reads the color of a single pixel.
+    pub fn read_pixel(&mut self, x: u16, y: u16) -> Result<u16, ()> {
+        let mut color = [0];
+        self.read_pixels(x, y, x, y, &mut color)?;
+        Ok(color[0])
+    }
+
+    /// Reads the display power mode (`RDDPM`).
+    pub fn read_power_mode(&mut self) -> Result<u8, ()> {
+        self.read_register(Instruction::RDDPM)
+    }
+
+    /// Checks whether the controller has to be initialized again, e.g. after the MCU reset
+    /// while the display kept its power.
+    ///
+    /// Reads the power mode (`RDDPM`) and returns `false` if the controller is out of sleep
+    /// with its display on, as left by `init`. In that case the driver state is updated as if
+    /// `init` had run, so drawing works without repeating it. The controller keeps the
+    /// registers set before the reset, so this is only correct if the driver is configured the
+    /// same way. A response of `0xFF`, as read from a floating data line, requires a new init.
+    pub fn needs_reinit(&mut self) -> Result<bool, ()> {
+        let power_mode = self.read_power_mode()?;
+        let running = power_mode != 0xFF && power_mode & 0x14 == 0x14;
+        if running {
+            self.initialized = true;
+            self.sleeping = false;
+            self.display_on = true;
+        }
+        Ok(!running)
+    }
+
+    /// Reads the memory access control register (`RDDMADCTL`) as last set by `MADCTL`.
+    pub fn read_madctl(&mut self) -> Result<u8, ()> {
+        self.read_register(Instruction::RDDMADCTL)
+    }
+
+    /// Blends `overlay_color` over the given drawing window with opacity `alpha`, e.g. to dim
+    /// the screen behind a dialog.
+    ///
+    /// With `background_read` the current pixels are read back with `RAMRD`, blended and
+    /// written again in small chunks, which costs two transfers per pixel. Without it the window
+    /// is assumed to be black and filled with the overlay color scaled by `alpha`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blend_region(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        overlay_color: u16,
+        alpha: u8,
+        background_read: bool,
+    ) -> Result<(), ()> {
+        if !background_read {
+            return self.fill_window(sx, sy, ex, ey, color::scale565(overlay_color, alpha));
+        }
+        self.map_region(sx, sy, ex, ey, |color| {
+            color::blend565(overlay_color, color, alpha)
+        })
+    }
+
+    /// Inverts the colors of the given drawing window, e.g. for a reverse video cursor.
+    ///
+    /// The pixels are read back with `RAMRD`, complemented and written again. Reads return 18
+    /// bits per pixel, which are truncated to RGB565 before inverting; pixels written as RGB565
+    /// come back unchanged.
+    pub fn invert_region(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), ()> {
+        self.map_region(sx, sy, ex, ey, |color| !color)
+    }
+
+    /// Reads the given drawing window back in small chunks and writes each pixel again after
+    /// passing it through `f`. Returns an error without sending anything if the window is
+    /// swapped or doesn't lie within the display.
+    fn map_region<F>(&mut self, sx: u16, sy: u16, ex: u16, ey: u16, f: F) -> Result<(), ()>
+    where
+        F: Fn(u16) -> u16,
+    {
+        if ex < sx || ey < sy || u32::from(ex) >= self.width || u32::from(ey) >= self.height {
+            return Err(());
+        }
+        let mut buffer = [0; 32];
+        for y in sy..=ey {
+            for x in (sx..=ex).step_by(buffer.len()) {
+                let end = ex.min(x.saturating_add(buffer.len() as u16 - 1));
+                let colors = &mut buffer[0..usize::from(end - x) + 1];
+                self.read_pixels(x, y, end, y, colors)?;
+                self.set_pixels_buffered(x, y, end, y, colors.iter().map(|&color| f(color)))?;
+            }
+        }
+        Ok(())
     }
 
-    /// Sets a pixel color at the given coords.
-    pub fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), ()> {
-        self.set_address_window(x, y, x, y)?;
-        self.write_command(Instruction::RAMWR, &[])?;
-        self.start_data()?;
-        self.write_word(color)
+    /// Reads the interface pixel format (`RDDCOLMOD`) as last set by `COLMOD`.
+    ///
+    /// Returns the three format bits, which equal `pixel_format() as u8` when the controller
+    /// uses the format the driver encodes pixels in.
+    pub fn read_pixel_format(&mut self) -> Result<u8, ()> {
+        Ok(self.read_register(Instruction::RDDCOLMOD)? & 0x07)
     }
 
-    /// Writes pixel colors sequentially into the current drawing window
-    pub fn write_pixels<P: IntoIterator<Item = u16>>(&mut self, colors: P) -> Result<(), ()> {
-        self.write_command(Instruction::RAMWR, &[])?;
+    /// Reads the display signal mode (`RDDSM`), which holds the tearing effect settings.
+    pub fn read_signal_mode(&mut self) -> Result<u8, ()> {
+        self.read_register(Instruction::RDDSM)
+    }
+
+    /// Reads the display signal mode and decodes its tearing effect bits, e.g. to check that a
+    /// `TEON` command was accepted.
+    pub fn signal_mode(&mut self) -> Result<SignalMode, ()> {
+        self.read_signal_mode().map(SignalMode::from)
+    }
+
+    /// Reads the self-diagnostic result register (`RDDSDR`).
+    pub fn read_self_diagnostic(&mut self) -> Result<u8, ()> {
+        self.read_register(Instruction::RDDSDR)
+    }
+
+    /// Reads the self-diagnostic result register and decodes its check bits.
+    pub fn self_diagnostic(&mut self) -> Result<SelfDiagnostic, ()> {
+        self.read_self_diagnostic().map(SelfDiagnostic::from)
+    }
+
+    /// Reads the three display identification bytes (`RDDID`).
+    ///
+    /// The dummy bytes the controller sends before the response are discarded like for
+    /// `read_pixels`, see `set_read_dummy_bytes`.
+    pub fn read_id(&mut self) -> Result<[u8; 3], ()> {
+        self.write_command(Instruction::RDDID, &[])?;
         self.start_data()?;
-        for color in colors {
-            self.write_word(color)?;
+        self.skip_dummy_bytes()?;
+        let mut id = [0; 3];
+        self.read_data(&mut id)?;
+        Ok(id)
+    }
+
+    /// Identifies the attached controller from its `RDDID` bytes.
+    ///
+    /// The first byte is the manufacturer ID (`0x7C` for Sitronix parts), the second the
+    /// module/driver version and the third the driver ID. The ST7735, ST7735R and ST7735S all
+    /// report `7C 89 F0`, so they can't be told apart this way and are all returned as
+    /// `Controller::ST7735`. Unrecognized IDs are returned as `Controller::Unknown` with the raw
+    /// bytes.
+    pub fn detect_controller(&mut self) -> Result<Controller, ()> {
+        Ok(match self.read_id()? {
+            [0x7C, 0x89, 0xF0] => Controller::ST7735,
+            [0x85, 0x85, 0x52] => Controller::ST7789,
+            id => Controller::Unknown(id),
+        })
+    }
+
+    /// Checks that the controller answers over SPI by reading back its ID.
+    ///
+    /// The driver can't configure the SPI mode itself, and a peripheral that isn't in mode 0
+    /// (CPOL = 0, CPHA = 0) usually reads back all zero or all one bits. That case is reported as
+    /// `SpiCheckError::SpiModeLikelyWrong`, which can also point to a disconnected MISO line.
+    pub fn verify_spi(&mut self) -> Result<(), SpiCheckError> {
+        let id = self.read_id().map_err(|_| SpiCheckError::Communication)?;
+        if is_floating(&id) {
+            Err(SpiCheckError::SpiModeLikelyWrong)
+        } else {
+            Ok(())
         }
-        Ok(())
     }
-    pub fn write_pixels_buffered<P: IntoIterator<Item = u16>>(
-        &mut self,
-        colors: P,
-    ) -> Result<(), ()> {
-        self.write_command(Instruction::RAMWR, &[])?;
-        self.start_data()?;
-        self.write_words_buffered(colors)
+
+    /// Checks whether the display still responds, e.g. to detect a loose cable.
+    ///
+    /// Reads the display ID and returns `false` if the read fails or every byte is `0x00` or
+    /// `0xFF`, as read from a data line that isn't driven. This can't tell a missing display
+    /// from one that returns such an ID, and a pull-up or pull-down on the data line decides
+    /// which of the two values a disconnected display reads as.
+    pub fn is_connected(&mut self) -> bool {
+        self.read_id().is_ok_and(|id| !is_floating(&id))
     }
 
-    /// Sets pixel colors at the given drawing window
-    pub fn set_pixels<P: IntoIterator<Item = u16>>(
+    /// Reads pixel colors from the given drawing window into `colors`.
+    ///
+    /// Any window within the display can be read, including single pixels. The controller sends
+    /// 18 bits per pixel when reading regardless of the pixel format, which are converted to
+    /// RGB565.
+    pub fn read_pixels(
         &mut self,
         sx: u16,
         sy: u16,
         ex: u16,
         ey: u16,
-        colors: P,
+        colors: &mut [u16],
     ) -> Result<(), ()> {
         self.set_address_window(sx, sy, ex, ey)?;
-        self.write_pixels(colors)
+        self.write_command(Instruction::RAMRD, &[])?;
+        self.start_data()?;
+        self.skip_dummy_bytes()?;
+
+        let mut buffer = [0; 30];
+        for chunk in colors.chunks_mut(buffer.len() / 3) {
+            let bytes = &mut buffer[0..chunk.len() * 3];
+            self.read_data(bytes)?;
+            for (color, rgb) in chunk.iter_mut().zip(bytes.chunks(3)) {
+                *color = (u16::from(rgb[0] & 0xF8) << 8)
+                    | (u16::from(rgb[1] & 0xFC) << 3)
+                    | u16::from(rgb[2] >> 3);
+            }
+        }
+        if self.mirror_x {
+            for row in colors.chunks_mut(usize::from(ex - sx) + 1) {
+                row.reverse();
+            }
+        }
+        Ok(())
     }
+}
 
-    pub fn set_pixels_buffered<P: IntoIterator<Item = u16>>(
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8> + spi::Write<u16>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Writes pixel colors into the current drawing window using 16-bit SPI frames.
+    ///
+    /// The SPI peripheral must send `u16` words as single 16-bit frames, most significant bit
+    /// first. That puts each RGB565 color on the wire in the big-endian order the controller
+    /// expects, so the colors are sent without any conversion. Returns an error unless the
+    /// pixel format is RGB565 with big-endian byte order.
+    pub fn write_pixels_u16(&mut self, colors: &[u16]) -> Result<(), ()> {
+        if self.pixel_format != PixelFormat::Rgb565 || self.byte_order != ByteOrder::BigEndian {
+            return Err(());
+        }
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        if self.mirror_x {
+            let mut buffer = [0; GRAM_ROWS as usize];
+            let width = usize::from(self.window_width).clamp(1, buffer.len());
+            for row in colors.chunks(width) {
+                let reversed = &mut buffer[0..row.len()];
+                reversed.copy_from_slice(row);
+                reversed.reverse();
+                spi::Write::<u16>::write(&mut self.spi, reversed).map_err(|_| ())?;
+            }
+        } else {
+            spi::Write::<u16>::write(&mut self.spi, colors).map_err(|_| ())?;
+        }
+        #[cfg(feature = "stats")]
+        {
+            self.stats.bytes = self.stats.bytes.wrapping_add(colors.len() as u32 * 2);
+        }
+        Ok(())
+    }
+
+    /// Sets pixel colors at the given drawing window using 16-bit SPI frames.
+    ///
+    /// See `write_pixels_u16` for the requirements on the SPI peripheral.
+    pub fn set_pixels_u16(
         &mut self,
         sx: u16,
         sy: u16,
         ex: u16,
         ey: u16,
-        colors: P,
+        colors: &[u16],
     ) -> Result<(), ()> {
         self.set_address_window(sx, sy, ex, ey)?;
-        self.write_pixels_buffered(colors)
+        self.write_pixels_u16(colors)
+    }
+}
+
+/// Decoded display signal mode, see `signal_mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignalMode {
+    /// The tearing effect output line is enabled (bit 7).
+    pub tearing_effect: bool,
+    /// The tearing effect output signals horizontal as well as vertical blanking, as set by
+    /// `TEON` with parameter 1 (bit 6).
+    pub tearing_effect_hblank: bool,
+}
+
+impl From<u8> for SignalMode {
+    fn from(bits: u8) -> Self {
+        SignalMode {
+            tearing_effect: bits & 0x80 != 0,
+            tearing_effect_hblank: bits & 0x40 != 0,
+        }
+    }
+}
+
+/// Decoded self-diagnostic result, see `self_diagnostic`.
+///
+/// The controller sets a bit when the corresponding check after sleep out passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfDiagnostic {
+    /// The registers were loaded from the factory defaults and EEPROM correctly (bit 7).
+    pub register_loading: bool,
+    /// The display is working correctly (bit 6).
+    pub functionality: bool,
+}
+
+impl From<u8> for SelfDiagnostic {
+    fn from(bits: u8) -> Self {
+        SelfDiagnostic {
+            register_loading: bits & 0x80 != 0,
+            functionality: bits & 0x40 != 0,
+        }
+    }
+}
+
+/// Controller identified by `detect_controller`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Controller {
+    /// ST7735, ST7735R or ST7735S.
+    ST7735,
+    /// ST7789, which this driver doesn't support.
+    ST7789,
+    /// A controller with an unrecognized `RDDID` response.
+    Unknown([u8; 3]),
+}
+
+/// Error returned by `verify_spi`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpiCheckError {
+    /// Writing the command or reading the response failed.
+    Communication,
+    /// The ID read back as all `0x00` or all `0xFF`, which usually means the SPI peripheral is
+    /// not in mode 0.
+    SpiModeLikelyWrong,
+}
+
+/// Reset pin placeholder for displays whose reset line is not connected to a GPIO.
+pub struct NoResetPin;
+
+impl OutputPin for NoResetPin {
+    type Error = core::convert::Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<SPI, DC> ST7735<SPI, DC, NoResetPin>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+{
+    /// Creates a new driver instance for a display without a reset pin.
+    ///
+    /// `init` skips the hardware reset and relies on the `SWRESET` software reset only.
+    /// Without a reset line, a controller that stops responding to commands can only be
+    /// recovered by power cycling the display.
+    pub fn new_without_reset(
+        spi: SPI,
+        dc: DC,
+        rgb: bool,
+        inverted: bool,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self::with_reset(spi, dc, None, rgb, inverted, width, height)
     }
 }
 
@@ -249,14 +2325,111 @@ extern crate embedded_graphics;
 #[cfg(feature = "graphics")]
 use self::embedded_graphics::{
     draw_target::DrawTarget,
+    mono_font::MonoTextStyle,
     pixelcolor::{
         raw::{RawData, RawU16},
         Rgb565,
     },
     prelude::*,
     primitives::Rectangle,
+    text::{Baseline, Text},
 };
 
+#[cfg(feature = "graphics")]
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Converts a rectangle to the inclusive drawing window expected by the controller, clipped
+    /// to the display. Returns `None` if no part of the rectangle is on the display.
+    fn window_for(&self, rect: &Rectangle) -> Option<(u16, u16, u16, u16)> {
+        let area = rect.intersection(&Rectangle::new(Point::zero(), self.size()));
+        let bottom_right = area.bottom_right()?;
+        Some((
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        ))
+    }
+
+    /// Returns the panel size in its native portrait orientation.
+    fn physical_size(&self) -> (i32, i32) {
+        let (width, height) = (self.display_size.0 as i32, self.display_size.1 as i32);
+        match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => (width, height),
+            Orientation::Landscape | Orientation::LandscapeSwapped => (height, width),
+        }
+    }
+
+    /// Maps a point in the panel's native portrait orientation, e.g. from a touch controller,
+    /// to the drawing coordinates of the current orientation.
+    ///
+    /// The global offset only shifts the display RAM and is not part of this transform, and
+    /// neither is the viewport.
+    pub fn to_logical(&self, physical: Point) -> Point {
+        let (width, height) = self.physical_size();
+        match self.orientation {
+            Orientation::Portrait => physical,
+            Orientation::PortraitSwapped => {
+                Point::new(width - 1 - physical.x, height - 1 - physical.y)
+            }
+            Orientation::Landscape => Point::new(physical.y, width - 1 - physical.x),
+            Orientation::LandscapeSwapped => Point::new(height - 1 - physical.y, physical.x),
+        }
+    }
+
+    /// Maps a point in the drawing coordinates of the current orientation to the panel's native
+    /// portrait orientation. This is the inverse of `to_logical`.
+    pub fn to_physical(&self, logical: Point) -> Point {
+        let (width, height) = self.physical_size();
+        match self.orientation {
+            Orientation::Portrait => logical,
+            Orientation::PortraitSwapped => {
+                Point::new(width - 1 - logical.x, height - 1 - logical.y)
+            }
+            Orientation::Landscape => Point::new(width - 1 - logical.y, logical.x),
+            Orientation::LandscapeSwapped => Point::new(logical.y, height - 1 - logical.x),
+        }
+    }
+
+    /// Draws an image stored as big-endian RGB565 rows of `width` pixels.
+    ///
+    /// The data is sent as is in RGB565 mode, which makes this suitable for images stored in
+    /// flash. The image must fit on the display.
+    pub fn draw_image_raw(&mut self, top_left: Point, width: u16, data: &[u8]) -> Result<(), ()> {
+        let row_len = usize::from(width) * 2;
+        if top_left.x < 0 || top_left.y < 0 || row_len == 0 || !data.len().is_multiple_of(row_len) {
+            return Err(());
+        }
+        let height = u16::try_from(data.len() / row_len).map_err(|_| ())?;
+        if height == 0 {
+            return Ok(());
+        }
+
+        let sx = u16::try_from(top_left.x).map_err(|_| ())?;
+        let sy = u16::try_from(top_left.y).map_err(|_| ())?;
+        let (sx, sy, ex, ey) = window_of_size(sx, sy, width, height)?;
+        self.set_pixels_raw(sx, sy, ex, ey, data)
+    }
+
+    /// Draws `text` with an embedded-graphics mono font style, with `pos` as the top left
+    /// corner of the first line.
+    ///
+    /// With a background color in `style` every glyph is written as a single window, otherwise
+    /// only the foreground pixels are drawn. Returns the position following the last character.
+    pub fn draw_mono_text(
+        &mut self,
+        text: &str,
+        pos: Point,
+        style: MonoTextStyle<'_, Rgb565>,
+    ) -> Result<Point, ()> {
+        Text::with_baseline(text, pos, style, Baseline::Top).draw(self)
+    }
+}
+
 #[cfg(feature = "graphics")]
 impl<SPI, DC, RST> DrawTarget for ST7735<SPI, DC, RST>
 where
@@ -289,38 +2462,44 @@ where
         Ok(())
     }
 
+    /// Writes `colors` row by row into the part of `area` that is on the display, relying on
+    /// the controller wrapping to the next row at the right edge of the window.
     fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Self::Color>,
     {
-        // Clamp area to drawable part of the display target
-        let drawable_area = area.intersection(&Rectangle::new(Point::zero(), self.size()));
+        let (sx, sy, ex, ey) = match self.window_for(area) {
+            Some(window) => window,
+            None => return Ok(()),
+        };
+        let drawable_area = Rectangle::with_corners(
+            Point::new(sx.into(), sy.into()),
+            Point::new(ex.into(), ey.into()),
+        );
+        self.set_pixels_buffered(
+            sx,
+            sy,
+            ex,
+            ey,
+            area.points()
+                .zip(colors)
+                .filter(|(pos, _color)| drawable_area.contains(*pos))
+                .map(|(_pos, color)| RawU16::from(color).into_inner()),
+        )
+        .map(|_| ())
+    }
 
-        if drawable_area.size != Size::zero() {
-            self.set_pixels_buffered(
-                drawable_area.top_left.x as u16,
-                drawable_area.top_left.y as u16,
-                (drawable_area.top_left.x + (drawable_area.size.width - 1) as i32) as u16,
-                (drawable_area.top_left.y + (drawable_area.size.height - 1) as i32) as u16,
-                area.points()
-                    .zip(colors)
-                    .filter(|(pos, _color)| drawable_area.contains(*pos))
-                    .map(|(_pos, color)| RawU16::from(color).into_inner()),
-            )?;
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match self.window_for(area) {
+            Some((sx, sy, ex, ey)) => {
+                self.fill_window(sx, sy, ex, ey, RawU16::from(color).into_inner())
+            }
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-        self.set_pixels_buffered(
-            0,
-            0,
-            self.width as u16 - 1,
-            self.height as u16 - 1,
-            core::iter::repeat(RawU16::from(color).into_inner())
-                .take((self.width * self.height) as usize),
-        )
+        self.fill_screen(RawU16::from(color).into_inner())
     }
 }
 
@@ -335,3 +2514,651 @@ where
         Size::new(self.width, self.height)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::instruction::Instruction;
+    use crate::mock::{self, Bus, Delay};
+    use crate::{Controller, InitStep, Orientation, PixelFormat, ST7735};
+
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn init_sends_reset_and_default_sequence() {
+        let bus = Bus::default();
+        let mut display = ST7735::new(bus.spi(), bus.dc(), bus.rst(), true, false, 128, 160);
+        let mut delay = Delay::default();
+        display.init(&mut delay).unwrap();
+
+        assert_eq!(bus.reset_levels(), [true, false, true]);
+        assert_eq!(delay.waits, [10, 10, 200, 200, 200]);
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x01, vec![]),
+                (0x11, vec![]),
+                (0xB1, vec![0x01, 0x2C, 0x2D]),
+                (0xB2, vec![0x01, 0x2C, 0x2D]),
+                (0xB3, vec![0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D]),
+                (0xB4, vec![0x07]),
+                (0xC0, vec![0xA2, 0x02, 0x84]),
+                (0xC1, vec![0xC5]),
+                (0xC2, vec![0x0A, 0x00]),
+                (0xC3, vec![0x8A, 0x2A]),
+                (0xC4, vec![0x8A, 0xEE]),
+                (0xC5, vec![0x0E]),
+                (0x20, vec![]),
+                (0x36, vec![0x00]),
+                (0x3A, vec![0x05]),
+                (0x29, vec![]),
+            ]
+        );
+        assert!(display.is_initialized() && display.is_on() && !display.is_sleeping());
+    }
+
+    #[test]
+    fn set_pixel_sends_window_and_color() {
+        let (mut display, bus) = mock::display();
+        display.set_pixel(3, 5, 0xF800).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x2A, vec![0, 3, 0, 3]),
+                (0x2B, vec![0, 5, 0, 5]),
+                (0x2C, vec![0xF8, 0x00]),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_pixel_applies_offset() {
+        let (mut display, bus) = mock::display();
+        display.set_offset(2, 1);
+        display.set_pixel(3, 5, 0x001F).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x2A, vec![0, 5, 0, 5]),
+                (0x2B, vec![0, 6, 0, 6]),
+                (0x2C, vec![0x00, 0x1F]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_window_repeats_color() {
+        let (mut display, bus) = mock::display();
+        display.fill_window(0, 0, 9, 4, 0x07E0).unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 0, 0, 9]));
+        assert_eq!(commands[1], (0x2B, vec![0, 0, 0, 4]));
+        assert_eq!(commands[2].0, 0x2C);
+        assert_eq!(commands[2].1, [0x07, 0xE0].repeat(50));
+        assert_eq!(commands.len(), 3);
+    }
+
+    /// Returns the data written after `RAMWR` by `f`, with the pixel format set to `format`.
+    fn pixel_data<F>(format: PixelFormat, f: F) -> Vec<u8>
+    where
+        F: FnOnce(&mut ST7735<mock::Spi, mock::Pin, mock::Pin>),
+    {
+        let (mut display, bus) = mock::display();
+        display.set_pixel_format(format).unwrap();
+        bus.clear();
+        f(&mut display);
+        let commands = bus.commands();
+        let (command, data) = commands.last().unwrap();
+        assert_eq!(*command, 0x2C);
+        data.clone()
+    }
+
+    #[test]
+    fn streamed_pixels_are_encoded_in_pixel_format() {
+        let write = |display: &mut ST7735<_, _, _>| {
+            display
+                .set_pixels_buffered(0, 0, 2, 0, [0xF800, 0x001F, 0xF800])
+                .unwrap();
+        };
+        // Two pixels per three bytes, the unpaired last pixel is padded to two bytes.
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb444, write),
+            [0xF0, 0x00, 0x0F, 0xF0, 0x00]
+        );
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb565, write),
+            [0xF8, 0x00, 0x00, 0x1F, 0xF8, 0x00]
+        );
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb666, write),
+            [0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn repeated_pixels_are_encoded_in_pixel_format() {
+        let fill = |display: &mut ST7735<_, _, _>| {
+            display.fill_window(0, 0, 2, 0, 0x07E0).unwrap();
+        };
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb444, fill),
+            [0x0F, 0x00, 0xF0, 0x0F, 0x00]
+        );
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb565, fill),
+            [0x07, 0xE0, 0x07, 0xE0, 0x07, 0xE0]
+        );
+        assert_eq!(
+            pixel_data(PixelFormat::Rgb666, fill),
+            [0x00, 0xFC, 0x00, 0x00, 0xFC, 0x00, 0x00, 0xFC, 0x00]
+        );
+    }
+
+    #[test]
+    fn draw_scaled_1bpp_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        let data = [0xFF; 2];
+        assert_eq!(
+            display.draw_scaled_1bpp(0, 0, 8, 2, &data, 9000, 0xFFFF, 0),
+            Err(())
+        );
+        assert_eq!(
+            display.draw_scaled_1bpp(65530, 0, 8, 2, &data, 1, 0xFFFF, 0),
+            Err(())
+        );
+        assert!(bus.writes().is_empty());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn draw_image_raw_rejects_window_past_coordinate_range() {
+        use embedded_graphics::prelude::Point;
+
+        let (mut display, bus) = mock::display();
+        let data = [0; 2 * 10];
+        assert_eq!(
+            display.draw_image_raw(Point::new(65530, 0), 10, &data),
+            Err(())
+        );
+        assert_eq!(
+            display.draw_image_raw(Point::new(i32::MAX, 0), 10, &data),
+            Err(())
+        );
+        assert!(bus.writes().is_empty());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn touch_points_map_between_orientations() {
+        use embedded_graphics::prelude::Point;
+
+        // The driver size is given in the drawing orientation, the panel is 128x160 in portrait.
+        let corners = [(0, 0), (127, 0), (0, 159), (127, 159)];
+        let cases = [
+            (
+                Orientation::Portrait,
+                [(0, 0), (127, 0), (0, 159), (127, 159)],
+            ),
+            (
+                Orientation::PortraitSwapped,
+                [(127, 159), (0, 159), (127, 0), (0, 0)],
+            ),
+            (
+                Orientation::Landscape,
+                [(0, 127), (0, 0), (159, 127), (159, 0)],
+            ),
+            (
+                Orientation::LandscapeSwapped,
+                [(159, 0), (159, 127), (0, 0), (0, 127)],
+            ),
+        ];
+        for (orientation, logical) in cases {
+            let bus = mock::Bus::default();
+            let (width, height) = match orientation {
+                Orientation::Portrait | Orientation::PortraitSwapped => (128, 160),
+                Orientation::Landscape | Orientation::LandscapeSwapped => (160, 128),
+            };
+            let mut display =
+                ST7735::new(bus.spi(), bus.dc(), bus.rst(), true, false, width, height);
+            display.init(&mut mock::Delay::default()).unwrap();
+            display.set_orientation(&orientation).unwrap();
+            for (&(px, py), &(lx, ly)) in corners.iter().zip(logical.iter()) {
+                let physical = Point::new(px, py);
+                assert_eq!(display.to_logical(physical), Point::new(lx, ly));
+                assert_eq!(display.to_physical(Point::new(lx, ly)), physical);
+            }
+            for physical in [Point::new(3, 7), Point::new(100, 42)] {
+                assert_eq!(display.to_physical(display.to_logical(physical)), physical);
+            }
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_wrap_around() {
+        let (mut display, _bus) = mock::display();
+        display.take_stats();
+        display.stats.bytes = u32::MAX;
+        display.stats.commands = u32::MAX;
+        display.set_pixel(0, 0, 0).unwrap();
+        let stats = display.take_stats();
+        assert_eq!(stats.commands, 2);
+        assert_eq!(stats.bytes, 12);
+    }
+
+    #[test]
+    fn power_save_restores_power_of_init_sequence() {
+        let bus = Bus::default();
+        let mut display = ST7735::new(bus.spi(), bus.dc(), bus.rst(), true, false, 128, 160);
+        display
+            .run_init_sequence(
+                &mut Delay::default(),
+                &[InitStep::new(Instruction::PWCTR3, &[0x0D, 0x00])],
+            )
+            .unwrap();
+        bus.clear();
+        display.power_save(true).unwrap();
+        display.power_save(false).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [(0xC2, vec![0x00, 0x00]), (0xC2, vec![0x0D, 0x00])]
+        );
+    }
+
+    #[test]
+    fn set_pixels_rows_writes_each_row() {
+        let (mut display, bus) = mock::display();
+        display.set_pixels_rows(4, 6, [[1, 2], [3, 4]]).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x2A, vec![0, 4, 0, 5]),
+                (0x2B, vec![0, 6, 0, 6]),
+                (0x2C, vec![0, 1, 0, 2]),
+                (0x2B, vec![0, 7, 0, 7]),
+                (0x2C, vec![0, 3, 0, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_pixels_rows_stops_before_short_row() {
+        let (mut display, bus) = mock::display();
+        let rows = [&[1, 2][..], &[3][..], &[4, 5][..]];
+        assert_eq!(
+            display.set_pixels_rows(0, 0, rows.iter().map(|row| row.iter().copied())),
+            Err(())
+        );
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x2A, vec![0, 0, 0, 1]),
+                (0x2B, vec![0, 0, 0, 0]),
+                (0x2C, vec![0, 1, 0, 2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_pixels_rows_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.set_pixels_rows(65535, 0, [[1, 2]]), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn fill_n_wraps_to_next_rows() {
+        let (mut display, bus) = mock::display();
+        display
+            .fill_n(127, 158, 129, core::iter::repeat(1))
+            .unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 127, 0, 127]));
+        assert_eq!(commands[1], (0x2B, vec![0, 158, 0, 158]));
+        assert_eq!(commands[3], (0x2A, vec![0, 0, 0, 127]));
+        assert_eq!(commands[4], (0x2B, vec![0, 159, 0, 159]));
+        assert_eq!(commands[5].1.len(), 128 * 2);
+    }
+
+    #[test]
+    fn fill_n_rejects_count_past_display() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(
+            display.fill_n(127, 158, 130, core::iter::repeat(1)),
+            Err(())
+        );
+        assert_eq!(display.fill_n(0, 160, 1, core::iter::repeat(1)), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn read_id_skips_dummy_byte() {
+        let (mut display, bus) = mock::display();
+        bus.respond(&[0x00, 0x7C, 0x89, 0xF0]);
+        assert_eq!(display.detect_controller(), Ok(Controller::ST7735));
+        assert_eq!(bus.commands(), [(0x04, vec![])]);
+
+        bus.respond(&[0xFF, 0x7C, 0x89, 0xF0]);
+        assert!(display.is_connected());
+    }
+
+    #[test]
+    fn reversed_columns_are_rejected_without_traffic() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.set_address_window(5, 0, 4, 0), Err(()));
+        assert_eq!(display.set_column_window(5, 4), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn reversed_rows_are_rejected_without_traffic() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.set_address_window(0, 5, 0, 4), Err(()));
+        assert_eq!(display.set_row_window(5, 4), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn viewport_moves_and_bounds_windows() {
+        let (mut display, bus) = mock::display();
+        display.set_viewport((10, 20, 73, 159)).unwrap();
+        assert_eq!(display.fill_window(0, 0, 100, 0, 5), Err(()));
+        assert_eq!(display.set_pixel(64, 0, 5), Err(()));
+        assert_eq!(display.set_pixel(0, 140, 5), Err(()));
+        assert!(bus.writes().is_empty());
+
+        display.set_pixel(63, 139, 5).unwrap();
+        assert_eq!(bus.commands()[0], (0x2A, vec![0, 73, 0, 73]));
+        assert_eq!(bus.commands()[1], (0x2B, vec![0, 159, 0, 159]));
+        assert_eq!(bus.pixel(73, 159), 5);
+
+        display.clear_viewport();
+        display.set_pixel(100, 0, 5).unwrap();
+    }
+
+    #[test]
+    fn draw_paletted_4bpp_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(
+            display.draw_paletted_4bpp(65535, 0, 2, 1, &[0x01], &[0; 16]),
+            Err(())
+        );
+        assert_eq!(
+            display.draw_paletted_4bpp(0, 65535, 2, 2, &[0x01, 0x23], &[0; 16]),
+            Err(())
+        );
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn blit_region_keyed_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(
+            display.blit_region_keyed(65535, 0, 2, 1, &[1, 2], 0),
+            Err(())
+        );
+        assert_eq!(
+            display.blit_region_keyed(0, 65535, 1, 2, &[1, 2], 0),
+            Err(())
+        );
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn invert_region_rejects_window_past_display() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.invert_region(65530, 0, 65535, 0), Err(()));
+        assert_eq!(display.invert_region(0, 0, 0, 160), Err(()));
+        assert_eq!(display.invert_region(5, 0, 4, 0), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn with_offset_rejects_overflowing_offset() {
+        let (mut display, bus) = mock::display();
+        display.set_offset(2, 1);
+        assert_eq!(display.with_offset(1, u16::MAX, |_| Ok(())), Err(()));
+        assert_eq!(display.with_offset(u16::MAX, 1, |_| Ok(())), Err(()));
+        display
+            .with_offset(1, 1, |display| display.set_pixel(0, 0, 0))
+            .unwrap();
+        display.set_pixel(0, 0, 0).unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 3, 0, 3]));
+        assert_eq!(commands[1], (0x2B, vec![0, 2, 0, 2]));
+        assert_eq!(commands[3], (0x2A, vec![0, 2, 0, 2]));
+        assert_eq!(commands[4], (0x2B, vec![0, 1, 0, 1]));
+    }
+
+    /// Streams `count` gray pixels whose 4-bit channels all equal the pixel index modulo 16, and
+    /// returns the SPI writes of pixel data.
+    fn buffered_writes(format: PixelFormat, count: u16) -> Vec<Vec<u8>> {
+        let (mut display, bus) = mock::display();
+        display.set_pixel_format(format).unwrap();
+        display.set_address_window(0, 0, 127, 0).unwrap();
+        bus.clear();
+        let written = display
+            .write_pixels_buffered((0..count).map(|i| {
+                let v = i % 16;
+                (v << 12) | (v << 7) | (v << 1)
+            }))
+            .unwrap();
+        assert_eq!(written, u32::from(count));
+        let writes = bus.writes();
+        assert_eq!(writes[0], (false, vec![0x2C]));
+        writes[1..].iter().map(|(_, bytes)| bytes.clone()).collect()
+    }
+
+    fn lengths(writes: &[Vec<u8>]) -> Vec<usize> {
+        writes.iter().map(|bytes| bytes.len()).collect()
+    }
+
+    #[test]
+    fn buffered_rgb565_writes_flush_at_32_bytes() {
+        for (count, expected) in [
+            (15, vec![30]),
+            (16, vec![32]),
+            (17, vec![32, 2]),
+            (33, vec![32, 32, 2]),
+        ] {
+            let writes = buffered_writes(PixelFormat::Rgb565, count);
+            assert_eq!(lengths(&writes), expected, "{} pixels", count);
+            let bytes: Vec<u8> = (0..count)
+                .flat_map(|i| {
+                    let v = i % 16;
+                    ((v << 12) | (v << 7) | (v << 1)).to_be_bytes()
+                })
+                .collect();
+            assert_eq!(writes.concat(), bytes, "{} pixels", count);
+        }
+    }
+
+    #[test]
+    fn buffered_rgb444_writes_keep_pairs_across_flush() {
+        let writes = buffered_writes(PixelFormat::Rgb444, 23);
+        assert_eq!(lengths(&writes), [30, 5]);
+        let nibbles: Vec<u8> = (0..23u8).flat_map(|i| [i % 16; 3]).chain([0]).collect();
+        let bytes: Vec<u8> = nibbles
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair[1])
+            .collect();
+        assert_eq!(writes.concat(), bytes);
+    }
+
+    #[test]
+    fn buffered_rgb666_writes_keep_pixels_across_flush() {
+        let writes = buffered_writes(PixelFormat::Rgb666, 11);
+        assert_eq!(lengths(&writes), [30, 3]);
+        let bytes: Vec<u8> = (0..11u8)
+            .flat_map(|i| {
+                let r = (i % 16) << 1;
+                let g = (i % 16) << 2;
+                [r << 3 | r >> 2, g << 2, r << 3 | r >> 2]
+            })
+            .collect();
+        assert_eq!(writes.concat(), bytes);
+    }
+
+    #[test]
+    fn offset_is_checked_in_current_orientation() {
+        let bus = Bus::default();
+        let mut display = ST7735::new(bus.spi(), bus.dc(), bus.rst(), true, false, 160, 80);
+        display.init(&mut Delay::default()).unwrap();
+        display.set_offset(1, 26);
+        bus.clear();
+
+        // 160 columns don't fit the 132 column display RAM in portrait orientation.
+        assert_eq!(display.fill_screen(0), Err(()));
+        assert!(bus.writes().is_empty());
+
+        display.set_orientation(&Orientation::Landscape).unwrap();
+        bus.clear();
+        display.fill_screen(0).unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 1, 0, 160]));
+        assert_eq!(commands[1], (0x2B, vec![0, 26, 0, 105]));
+
+        display.set_offset(3, 26);
+        assert_eq!(display.fill_screen(0), Err(()));
+    }
+
+    #[test]
+    fn draw_cell_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.draw_cell(65535, 0, 2, 1, &[1, 2]), Err(()));
+        assert_eq!(display.draw_cell(0, 65535, 1, 2, &[1, 2]), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn mirrored_rows_are_reversed() {
+        let (mut display, bus) = mock::display();
+        display.set_mirror_x(true);
+        display.set_pixels(0, 0, 2, 0, [1, 2, 3]).unwrap();
+        display
+            .set_pixels_buffered(0, 1, 1, 2, [1, 2, 3, 4])
+            .unwrap();
+        display.set_pixels_slice(0, 3, 1, 3, &[5, 6]).unwrap();
+        let commands = bus.commands();
+        assert_eq!(commands[0], (0x2A, vec![0, 125, 0, 127]));
+        assert_eq!(commands[2], (0x2C, vec![0, 3, 0, 2, 0, 1]));
+        assert_eq!(commands[3], (0x2A, vec![0, 126, 0, 127]));
+        assert_eq!(commands[5], (0x2C, vec![0, 2, 0, 1, 0, 4, 0, 3]));
+        assert_eq!(commands[8], (0x2C, vec![0, 6, 0, 5]));
+    }
+
+    #[test]
+    fn mirrored_writer_reverses_rows() {
+        let (mut display, bus) = mock::display();
+        display.set_mirror_x(true);
+        let mut writer = display.begin_write(0, 0, 2, 1).unwrap();
+        writer.write_all([1, 2, 3, 4, 5, 6]).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(bus.commands()[0], (0x2A, vec![0, 125, 0, 127]));
+        assert_eq!(
+            bus.commands()[2],
+            (0x2C, vec![0, 3, 0, 2, 0, 1, 0, 6, 0, 5, 0, 4])
+        );
+        assert_eq!(
+            [bus.pixel(127, 0), bus.pixel(126, 0), bus.pixel(125, 0)],
+            [1, 2, 3]
+        );
+        assert_eq!(
+            [bus.pixel(127, 1), bus.pixel(126, 1), bus.pixel(125, 1)],
+            [4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn mirrored_read_returns_logical_order() {
+        let (mut display, bus) = mock::display();
+        display.set_mirror_x(true);
+        bus.respond(&[0, 0xF8, 0, 0, 0, 0xFC, 0, 0, 0, 0xF8]);
+        let mut colors = [0; 3];
+        display.read_pixels(0, 0, 2, 0, &mut colors).unwrap();
+        assert_eq!(colors, [0x001F, 0x07E0, 0xF800]);
+        assert_eq!(bus.commands()[0], (0x2A, vec![0, 125, 0, 127]));
+    }
+
+    #[test]
+    fn pixels_wrap_row_major_within_window() {
+        let (mut display, bus) = mock::display();
+        let colors = [
+            0x1111, 0x2222, 0x3333, 0x4444, 0x5555, 0x6666, 0x7777, 0x8888, 0x9999,
+        ];
+        display.set_address_window(4, 5, 6, 7).unwrap();
+        display.write_pixels(colors).unwrap();
+
+        let bytes: Vec<u8> = colors
+            .iter()
+            .flat_map(|color| color.to_be_bytes())
+            .collect();
+        assert_eq!(bus.commands()[2], (0x2C, bytes));
+        for (i, &color) in colors.iter().enumerate() {
+            assert_eq!(bus.pixel(4 + i % 3, 5 + i / 3), color, "pixel {}", i);
+        }
+        assert_eq!(bus.pixel(7, 5), 0);
+        assert_eq!(bus.pixel(4, 8), 0);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn mirrored_text_is_mirrored_with_and_without_background() {
+        use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder};
+        use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+        use embedded_graphics::prelude::Point;
+
+        let render = |mirror: bool, background: bool| {
+            let (mut display, bus) = mock::display();
+            display.set_mirror_x(mirror);
+            let style = MonoTextStyleBuilder::new()
+                .font(&FONT_6X10)
+                .text_color(Rgb565::WHITE);
+            let style = if background {
+                style.background_color(Rgb565::BLACK).build()
+            } else {
+                style.build()
+            };
+            display.draw_mono_text("R", Point::zero(), style).unwrap();
+            bus
+        };
+
+        let plain = render(false, false);
+        let mirrored = render(true, false);
+        let mirrored_background = render(true, true);
+        let mut foreground = 0;
+        for y in 0..10 {
+            for x in 0..6 {
+                let color = plain.pixel(x, y);
+                foreground += usize::from(color == 0xFFFF);
+                assert_eq!(mirrored.pixel(127 - x, y), color, "({}, {})", x, y);
+                assert_eq!(
+                    mirrored_background.pixel(127 - x, y),
+                    color,
+                    "({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+        assert!(foreground > 0);
+    }
+
+    #[test]
+    fn draw_bar_rejects_bar_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.draw_bar(0, 2, u16::MAX, 10, 1, 0), Err(()));
+        assert_eq!(display.draw_bar(u16::MAX, 2, 10, 5, 1, 0), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn read_madctl_returns_response() {
+        let (mut display, bus) = mock::display();
+        bus.respond(&[0x60]);
+        assert_eq!(display.read_madctl(), Ok(0x60));
+        assert_eq!(bus.commands(), [(0x0B, vec![])]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Mock SPI bus and pins that record the traffic sent to the display, for unit tests.
+
+extern crate std;
+
+use crate::ST7735;
+
+use core::convert::Infallible;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::vec;
+use std::vec::Vec;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+#[derive(Default)]
+struct State {
+    /// Current level of the data/command pin.
+    dc: bool,
+    /// Every SPI write with the data/command level it was sent at.
+    writes: Vec<(bool, Vec<u8>)>,
+    /// Every level the reset pin was driven to.
+    reset: Vec<bool>,
+    /// Bytes returned by reads, zero once exhausted.
+    responses: VecDeque<u8>,
+    /// Model of the display RAM, written by RGB565 pixel data.
+    gram: Gram,
+}
+
+/// Size of the display RAM model.
+const COLUMNS: usize = 132;
+const ROWS: usize = 162;
+
+/// Display RAM model that follows `CASET`, `RASET` and `RAMWR` like the controller.
+struct Gram {
+    pixels: Vec<u16>,
+    command: u8,
+    params: Vec<u8>,
+    columns: (u16, u16),
+    rows: (u16, u16),
+    cursor: (u16, u16),
+    high_byte: Option<u8>,
+}
+
+impl Default for Gram {
+    fn default() -> Self {
+        Gram {
+            pixels: vec![0; COLUMNS * ROWS],
+            command: 0,
+            params: Vec::new(),
+            columns: (0, COLUMNS as u16 - 1),
+            rows: (0, ROWS as u16 - 1),
+            cursor: (0, 0),
+            high_byte: None,
+        }
+    }
+}
+
+impl Gram {
+    fn command(&mut self, command: u8) {
+        self.command = command;
+        self.params.clear();
+        if command == 0x2C {
+            self.cursor = (self.columns.0, self.rows.0);
+            self.high_byte = None;
+        }
+    }
+
+    fn data(&mut self, byte: u8) {
+        match self.command {
+            0x2A | 0x2B => {
+                self.params.push(byte);
+                if self.params.len() == 4 {
+                    let p = &self.params;
+                    let range = (
+                        u16::from_be_bytes([p[0], p[1]]),
+                        u16::from_be_bytes([p[2], p[3]]),
+                    );
+                    if self.command == 0x2A {
+                        self.columns = range;
+                    } else {
+                        self.rows = range;
+                    }
+                }
+            }
+            0x2C => match self.high_byte.take() {
+                None => self.high_byte = Some(byte),
+                Some(high) => {
+                    let (x, y) = self.cursor;
+                    if usize::from(x) < COLUMNS && usize::from(y) < ROWS {
+                        self.pixels[usize::from(y) * COLUMNS + usize::from(x)] =
+                            u16::from_be_bytes([high, byte]);
+                    }
+                    // Continue at the start of the next row past the window's right edge.
+                    self.cursor = if x >= self.columns.1 {
+                        (self.columns.0, y + 1)
+                    } else {
+                        (x + 1, y)
+                    };
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Shared recording of the traffic on a mock SPI bus and its pins.
+#[derive(Clone, Default)]
+pub struct Bus(Rc<RefCell<State>>);
+
+impl Bus {
+    pub fn spi(&self) -> Spi {
+        Spi(self.clone())
+    }
+
+    pub fn dc(&self) -> Pin {
+        Pin {
+            bus: self.clone(),
+            dc: true,
+        }
+    }
+
+    pub fn rst(&self) -> Pin {
+        Pin {
+            bus: self.clone(),
+            dc: false,
+        }
+    }
+
+    /// Returns every SPI write as `(data, bytes)`, where `data` is the level of the
+    /// data/command pin.
+    pub fn writes(&self) -> Vec<(bool, Vec<u8>)> {
+        self.0.borrow().writes.clone()
+    }
+
+    /// Returns every command sent, together with all data bytes that followed it.
+    pub fn commands(&self) -> Vec<(u8, Vec<u8>)> {
+        let mut commands: Vec<(u8, Vec<u8>)> = Vec::new();
+        for (data, bytes) in self.writes() {
+            if data {
+                commands
+                    .last_mut()
+                    .expect("data sent before any command")
+                    .1
+                    .extend(bytes);
+            } else {
+                commands.extend(bytes.into_iter().map(|command| (command, Vec::new())));
+            }
+        }
+        commands
+    }
+
+    /// Returns the color of a pixel in the display RAM model, which assumes RGB565 data.
+    pub fn pixel(&self, x: usize, y: usize) -> u16 {
+        self.0.borrow().gram.pixels[y * COLUMNS + x]
+    }
+
+    /// Returns the levels the reset pin was driven to.
+    pub fn reset_levels(&self) -> Vec<bool> {
+        self.0.borrow().reset.clone()
+    }
+
+    /// Queues bytes to be returned by the following reads.
+    pub fn respond(&self, bytes: &[u8]) {
+        self.0.borrow_mut().responses.extend(bytes);
+    }
+
+    /// Forgets all recorded traffic.
+    pub fn clear(&self) {
+        let mut state = self.0.borrow_mut();
+        state.writes.clear();
+        state.reset.clear();
+    }
+}
+
+pub struct Spi(Bus);
+
+impl spi::Write<u8> for Spi {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let mut state = (self.0).0.borrow_mut();
+        let dc = state.dc;
+        state.writes.push((dc, words.to_vec()));
+        for &byte in words {
+            if dc {
+                state.gram.data(byte);
+            } else {
+                state.gram.command(byte);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl spi::Transfer<u8> for Spi {
+    type Error = Infallible;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+        let mut state = (self.0).0.borrow_mut();
+        for word in words.iter_mut() {
+            *word = state.responses.pop_front().unwrap_or(0);
+        }
+        Ok(words)
+    }
+}
+
+/// Data/command or reset pin of a mock bus.
+pub struct Pin {
+    bus: Bus,
+    dc: bool,
+}
+
+impl Pin {
+    fn set(&mut self, level: bool) {
+        let mut state = self.bus.0.borrow_mut();
+        if self.dc {
+            state.dc = level;
+        } else {
+            state.reset.push(level);
+        }
+    }
+}
+
+impl OutputPin for Pin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set(true);
+        Ok(())
+    }
+}
+
+/// Delay that returns right away, recording the requested waits in milliseconds.
+#[derive(Default)]
+pub struct Delay {
+    pub waits: Vec<u32>,
+}
+
+impl DelayMs<u8> for Delay {
+    fn delay_ms(&mut self, ms: u8) {
+        self.waits.push(ms.into());
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for Delay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.waits.push(ns / 1_000_000);
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.waits.push(ms);
+    }
+}
+
+/// Polls `future` until it completes.
+#[cfg(feature = "async")]
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Creates an initialized 128x160 display on a new bus, with the init traffic cleared.
+pub fn display() -> (ST7735<Spi, Pin, Pin>, Bus) {
+    let bus = Bus::default();
+    let mut display = ST7735::new(bus.spi(), bus.dc(), bus.rst(), true, false, 128, 160);
+    display.init(&mut Delay::default()).unwrap();
+    bus.clear();
+    (display, bus)
+}
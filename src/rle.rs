@@ -0,0 +1,87 @@
+use crate::instruction::Instruction;
+use crate::{window_of_size, PixelFormat, ST7735};
+
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Draws a run-length encoded image of `width` x `height` pixels.
+    ///
+    /// `data` is a sequence of 3 byte runs: a pixel count from 1 to 255 followed by a big-endian
+    /// RGB565 color. Runs continue across row ends. Returns an error without drawing anything
+    /// if the data is malformed, the runs don't add up to `width * height` pixels or the image
+    /// doesn't fit the coordinate range.
+    pub fn draw_rle(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+    ) -> Result<(), ()> {
+        if !data.len().is_multiple_of(3) || data.chunks(3).any(|run| run[0] == 0) {
+            return Err(());
+        }
+        let total: u32 = data.chunks(3).map(|run| u32::from(run[0])).sum();
+        if total != u32::from(width) * u32::from(height) {
+            return Err(());
+        }
+        if total == 0 {
+            return Ok(());
+        }
+
+        let (sx, sy, ex, ey) = window_of_size(sx, sy, width, height)?;
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        let runs = data
+            .chunks(3)
+            .map(|run| (u32::from(run[0]), u16::from_be_bytes([run[1], run[2]])));
+        if self.pixel_format == PixelFormat::Rgb444 || self.mirror_x {
+            // Pixels are packed in pairs, so runs of odd length can't be written separately, and
+            // mirrored rows have to be buffered to reverse them.
+            return self
+                .write_words_buffered(
+                    runs.flat_map(|(count, color)| core::iter::repeat_n(color, count as usize)),
+                )
+                .map(|_| ());
+        }
+        for (count, color) in runs {
+            self.write_repeated(color, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::mock;
+
+    use std::vec;
+
+    #[test]
+    fn draw_rle_writes_runs() {
+        let (mut display, bus) = mock::display();
+        display
+            .draw_rle(0, 0, 3, 1, &[2, 0xF8, 0x00, 1, 0x00, 0x1F])
+            .unwrap();
+        assert_eq!(
+            bus.commands()[2],
+            (0x2C, vec![0xF8, 0x00, 0xF8, 0x00, 0x00, 0x1F])
+        );
+    }
+
+    #[test]
+    fn draw_rle_rejects_window_past_coordinate_range() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(display.draw_rle(65535, 0, 2, 1, &[2, 0, 0]), Err(()));
+        assert!(bus.writes().is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+//! Filled and outlined shapes drawn with window fills.
+
+use crate::ST7735;
+
+use embedded_graphics::prelude::Point;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use micromath::F32Ext;
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Fills the rectangle from (`x0`, `y0`) to (`x1`, `y1`) (inclusive), clipped to the display.
+    fn fill_clipped(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u16) -> Result<(), ()> {
+        let x0 = x0.max(0);
+        let y0 = y0.max(0);
+        let x1 = x1.min(self.width as i32 - 1);
+        let y1 = y1.min(self.height as i32 - 1);
+        if x0 > x1 || y0 > y1 {
+            return Ok(());
+        }
+        self.fill_window(x0 as u16, y0 as u16, x1 as u16, y1 as u16, color)
+    }
+
+    /// Fills the horizontal span from `x0` to `x1` (inclusive) on row `y`, clipped to the display.
+    fn fill_span(&mut self, y: i32, x0: i32, x1: i32, color: u16) -> Result<(), ()> {
+        self.fill_clipped(x0, y, x1, y, color)
+    }
+
+    /// Sets a single pixel if it is on the display.
+    fn plot(&mut self, x: i32, y: i32, color: u16) -> Result<(), ()> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return Ok(());
+        }
+        self.set_pixel(x as u16, y as u16, color)
+    }
+
+    /// Draws a filled triangle, one window fill per scanline.
+    pub fn fill_triangle(&mut self, p0: Point, p1: Point, p2: Point, color: u16) -> Result<(), ()> {
+        let mut vertices = [p0, p1, p2];
+        vertices.sort_unstable_by_key(|p| p.y);
+        let [a, b, c] = vertices;
+
+        if a.y == c.y {
+            let x0 = a.x.min(b.x).min(c.x);
+            let x1 = a.x.max(b.x).max(c.x);
+            return self.fill_span(a.y, x0, x1, color);
+        }
+
+        for y in a.y.max(0)..=c.y.min(self.height as i32 - 1) {
+            let xa = edge_x(a, c, y);
+            let xb = if y < b.y {
+                edge_x(a, b, y)
+            } else {
+                edge_x(b, c, y)
+            };
+            self.fill_span(y, xa.min(xb), xa.max(xb), color)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a line from `start` to `end`, clipped to the display.
+    ///
+    /// Horizontal and vertical lines are drawn as a single window fill.
+    pub fn draw_line(&mut self, start: Point, end: Point, color: u16) -> Result<(), ()> {
+        self.draw_segment(start, end, color, true)
+    }
+
+    /// Draws lines connecting consecutive `points`. Points shared by two segments are drawn
+    /// once.
+    pub fn draw_polyline(&mut self, points: &[Point], color: u16) -> Result<(), ()> {
+        match points {
+            [] => Ok(()),
+            [point] => self.plot(point.x, point.y, color),
+            _ => {
+                for (i, segment) in points.windows(2).enumerate() {
+                    let last = i == points.len() - 2;
+                    self.draw_segment(segment[0], segment[1], color, last)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Draws a quadratic Bézier curve from `p0` to `p1`, bent towards `control`.
+    ///
+    /// The curve is approximated by `segments` straight lines, so more segments give a smoother
+    /// curve at the cost of speed. A segment count of 0 is treated as 1.
+    pub fn draw_quad_bezier(
+        &mut self,
+        p0: Point,
+        control: Point,
+        p1: Point,
+        color: u16,
+        segments: u16,
+    ) -> Result<(), ()> {
+        let n = i64::from(segments.max(1));
+        let point_at = |i: i64| {
+            let (a, b) = (n - i, i);
+            let coordinate = |c0: i32, c1: i32, c2: i32| {
+                let sum = a * a * i64::from(c0) + 2 * a * b * i64::from(c1) + b * b * i64::from(c2);
+                let denominator = n * n;
+                (sum + sum.signum() * denominator / 2) / denominator
+            };
+            Point::new(
+                coordinate(p0.x, control.x, p1.x) as i32,
+                coordinate(p0.y, control.y, p1.y) as i32,
+            )
+        };
+        let mut previous = p0;
+        for i in 1..=n {
+            let point = point_at(i);
+            self.draw_segment(previous, point, color, i == n)?;
+            previous = point;
+        }
+        Ok(())
+    }
+
+    /// Draws a line, leaving out its end point unless `include_end` is set.
+    fn draw_segment(
+        &mut self,
+        start: Point,
+        end: Point,
+        color: u16,
+        include_end: bool,
+    ) -> Result<(), ()> {
+        if start == end {
+            return if include_end {
+                self.plot(start.x, start.y, color)
+            } else {
+                Ok(())
+            };
+        }
+
+        if start.x == end.x || start.y == end.y {
+            let step = Point::new((end.x - start.x).signum(), (end.y - start.y).signum());
+            let end = if include_end { end } else { end - step };
+            return self.fill_clipped(
+                start.x.min(end.x),
+                start.y.min(end.y),
+                start.x.max(end.x),
+                start.y.max(end.y),
+                color,
+            );
+        }
+
+        line_points(start, end, |x, y| {
+            if !include_end && x == end.x && y == end.y {
+                Ok(())
+            } else {
+                self.plot(x, y, color)
+            }
+        })
+    }
+
+    /// Draws a dashed line, repeating `on` drawn pixels followed by `off` skipped pixels from
+    /// `start`.
+    ///
+    /// Horizontal and vertical lines draw every dash as a single window fill.
+    pub fn draw_dashed_line(
+        &mut self,
+        start: Point,
+        end: Point,
+        color: u16,
+        on: u16,
+        off: u16,
+    ) -> Result<(), ()> {
+        if on == 0 {
+            return Ok(());
+        }
+        let on = i32::from(on);
+        let period = on + i32::from(off);
+
+        if start.x == end.x || start.y == end.y {
+            let length = (end.x - start.x).abs().max((end.y - start.y).abs()) + 1;
+            let step = Point::new((end.x - start.x).signum(), (end.y - start.y).signum());
+            let mut i = 0;
+            while i < length {
+                let a = start + step * i;
+                let b = start + step * (i + on - 1).min(length - 1);
+                self.fill_clipped(
+                    a.x.min(b.x),
+                    a.y.min(b.y),
+                    a.x.max(b.x),
+                    a.y.max(b.y),
+                    color,
+                )?;
+                i += period;
+            }
+            return Ok(());
+        }
+
+        let mut i = 0;
+        line_points(start, end, |x, y| {
+            let visible = i % period < on;
+            i += 1;
+            if visible {
+                self.plot(x, y, color)
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Draws the outline of `area` with alternating drawn and skipped pixels.
+    ///
+    /// The pattern runs clockwise around the outline from the top left corner. Odd `phase`
+    /// values shift it by one pixel, so incrementing `phase` every frame animates "marching
+    /// ants".
+    pub fn draw_dotted_rect(&mut self, area: &Rectangle, color: u16, phase: u16) -> Result<(), ()> {
+        let bottom_right = match area.bottom_right() {
+            Some(point) => point,
+            None => return Ok(()),
+        };
+        let Point { x: x0, y: y0 } = area.top_left;
+        let Point { x: x1, y: y1 } = bottom_right;
+
+        let mut i = u32::from(phase);
+        let mut dot = |x: i32, y: i32| {
+            let visible = i.is_multiple_of(2);
+            i += 1;
+            if visible {
+                self.plot(x, y, color)
+            } else {
+                Ok(())
+            }
+        };
+        for x in x0..=x1 {
+            dot(x, y0)?;
+        }
+        for y in y0 + 1..=y1 {
+            dot(x1, y)?;
+        }
+        if y1 > y0 {
+            for x in (x0..x1).rev() {
+                dot(x, y1)?;
+            }
+        }
+        if x1 > x0 {
+            for y in (y0 + 1..y1).rev() {
+                dot(x0, y)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws the part of a circle outline between `start_deg` and `end_deg`.
+    ///
+    /// Angles are in degrees, measured clockwise from the positive x axis. The arc is drawn
+    /// clockwise from `start_deg` to `end_deg` and wraps past 360°, so 300° to 60° draws a
+    /// 120° arc through 0°.
+    pub fn draw_arc(
+        &mut self,
+        center: Point,
+        radius: u16,
+        start_deg: f32,
+        end_deg: f32,
+        color: u16,
+    ) -> Result<(), ()> {
+        let start = normalize_degrees(start_deg);
+        let sweep = if end_deg - start_deg >= 360.0 {
+            360.0
+        } else {
+            normalize_degrees(end_deg - start_deg)
+        };
+        let in_arc = |x: i32, y: i32| {
+            let angle = normalize_degrees(F32Ext::atan2(y as f32, x as f32).to_degrees());
+            normalize_degrees(angle - start) <= sweep
+        };
+
+        let Point { x: cx, y: cy } = center;
+        let mut x = i32::from(radius);
+        let mut y = 0;
+        let mut error = 1 - x;
+        while x >= y {
+            for &(px, py) in &[
+                (x, y),
+                (y, x),
+                (-y, x),
+                (-x, y),
+                (-x, -y),
+                (-y, -x),
+                (y, -x),
+                (x, -y),
+            ] {
+                if in_arc(px, py) {
+                    self.plot(cx + px, cy + py, color)?;
+                }
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a filled ellipse with radii `rx` and `ry`, one window fill per scanline.
+    pub fn fill_ellipse(&mut self, center: Point, rx: u16, ry: u16, color: u16) -> Result<(), ()> {
+        let Point { x: cx, y: cy } = center;
+        // Only the widest point of each row is filled.
+        let mut row: Option<(i32, i32)> = None;
+        ellipse_points(rx, ry, |x, y| {
+            if let Some((row_y, row_x)) = row {
+                if row_y != y {
+                    self.fill_span(cy - row_y, cx - row_x, cx + row_x, color)?;
+                    if row_y != 0 {
+                        self.fill_span(cy + row_y, cx - row_x, cx + row_x, color)?;
+                    }
+                }
+            }
+            row = Some((y, x));
+            Ok(())
+        })?;
+        if let Some((row_y, row_x)) = row {
+            self.fill_span(cy - row_y, cx - row_x, cx + row_x, color)?;
+            if row_y != 0 {
+                self.fill_span(cy + row_y, cx - row_x, cx + row_x, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws the outline of an ellipse with radii `rx` and `ry`.
+    pub fn draw_ellipse(&mut self, center: Point, rx: u16, ry: u16, color: u16) -> Result<(), ()> {
+        let Point { x: cx, y: cy } = center;
+        ellipse_points(rx, ry, |x, y| {
+            self.plot(cx + x, cy + y, color)?;
+            if x != 0 {
+                self.plot(cx - x, cy + y, color)?;
+            }
+            if y != 0 {
+                self.plot(cx + x, cy - y, color)?;
+                if x != 0 {
+                    self.plot(cx - x, cy - y, color)?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Maps an angle in degrees to the range `0.0..360.0`.
+fn normalize_degrees(angle: f32) -> f32 {
+    let angle = angle % 360.0;
+    if angle < 0.0 {
+        angle + 360.0
+    } else {
+        angle
+    }
+}
+
+/// Walks the line from `start` to `end` (inclusive) with Bresenham's algorithm, calling `f`
+/// with every point.
+fn line_points<F>(start: Point, end: Point, mut f: F) -> Result<(), ()>
+where
+    F: FnMut(i32, i32) -> Result<(), ()>,
+{
+    let dx = (end.x - start.x).abs();
+    let dy = -(end.y - start.y).abs();
+    let step_x = if start.x < end.x { 1 } else { -1 };
+    let step_y = if start.y < end.y { 1 } else { -1 };
+    let mut error = dx + dy;
+    let Point { mut x, mut y } = start;
+    loop {
+        f(x, y)?;
+        if x == end.x && y == end.y {
+            return Ok(());
+        }
+        let error2 = 2 * error;
+        if error2 >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if error2 <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// Walks the first quadrant of an ellipse with the midpoint algorithm, calling `f` with every
+/// point from (0, `ry`) to (`rx`, 0). The y coordinate never increases between calls.
+fn ellipse_points<F>(rx: u16, ry: u16, mut f: F) -> Result<(), ()>
+where
+    F: FnMut(i32, i32) -> Result<(), ()>,
+{
+    if ry == 0 {
+        for x in 0..=i32::from(rx) {
+            f(x, 0)?;
+        }
+        return Ok(());
+    }
+
+    let rx2 = i64::from(rx) * i64::from(rx);
+    let ry2 = i64::from(ry) * i64::from(ry);
+    let mut x: i64 = 0;
+    let mut y = i64::from(ry);
+    let mut px = 0;
+    let mut py = 2 * rx2 * y;
+
+    let mut p = ry2 - rx2 * y + rx2 / 4;
+    while px < py {
+        f(x as i32, y as i32)?;
+        x += 1;
+        px += 2 * ry2;
+        if p < 0 {
+            p += ry2 + px;
+        } else {
+            y -= 1;
+            py -= 2 * rx2;
+            p += ry2 + px - py;
+        }
+    }
+
+    p = ry2 * (2 * x + 1) * (2 * x + 1) / 4 + rx2 * (y - 1) * (y - 1) - rx2 * ry2;
+    while y >= 0 {
+        f(x as i32, y as i32)?;
+        y -= 1;
+        py -= 2 * rx2;
+        if p > 0 {
+            p += rx2 - py;
+        } else {
+            x += 1;
+            px += 2 * ry2;
+            p += rx2 - py + px;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the x coordinate of the edge from `from` to `to` on row `y`.
+fn edge_x(from: Point, to: Point, y: i32) -> i32 {
+    if from.y == to.y {
+        return from.x;
+    }
+    from.x + (to.x - from.x) * (y - from.y) / (to.y - from.y)
+}
@@ -0,0 +1,221 @@
+use core::ops::Deref;
+
+use crate::{Orientation, ST7735};
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// ST7735 driver with the display dimensions as part of its type.
+///
+/// The dimensions are available as constants, so buffers can be sized from the display type,
+/// e.g. `[0u16; Display::PIXELS]`. Only methods that keep the drawable area at `W` x `H` are
+/// available, so methods such as `set_viewport` can't invalidate the constants. The driver's
+/// getters are available through `Deref`, and `into_inner` gives access to the full API.
+pub struct ST7735Sized<SPI, DC, RST, const W: u16, const H: u16>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    display: ST7735<SPI, DC, RST>,
+}
+
+impl<SPI, DC, RST, const W: u16, const H: u16> ST7735Sized<SPI, DC, RST, W, H>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Display width in pixels.
+    pub const WIDTH: u16 = W;
+
+    /// Display height in pixels.
+    pub const HEIGHT: u16 = H;
+
+    /// Number of pixels on the display.
+    pub const PIXELS: usize = W as usize * H as usize;
+
+    /// Creates a new driver instance that uses hardware SPI.
+    pub fn new(spi: SPI, dc: DC, rst: RST, rgb: bool, inverted: bool) -> Self {
+        ST7735Sized {
+            display: ST7735::new(spi, dc, rst, rgb, inverted, W.into(), H.into()),
+        }
+    }
+
+    /// Returns the underlying driver.
+    pub fn into_inner(self) -> ST7735<SPI, DC, RST> {
+        self.display
+    }
+
+    /// Runs commands to initialize the display, see `ST7735::init`.
+    pub fn init<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.display.init(delay)
+    }
+
+    /// Puts the display into sleep mode.
+    pub fn sleep<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.display.sleep(delay)
+    }
+
+    /// Wakes the display from sleep mode.
+    pub fn wake<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.display.wake(delay)
+    }
+
+    /// Sets the display orientation.
+    pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), ()> {
+        self.display.set_orientation(orientation)
+    }
+
+    /// Sets the global offset of the displayed image, see `ST7735::set_offset`.
+    pub fn set_offset(&mut self, dx: u16, dy: u16) {
+        self.display.set_offset(dx, dy)
+    }
+
+    /// Sets a pixel color at the given coords.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), ()> {
+        self.display.set_pixel(x, y, color)
+    }
+
+    /// Sets pixel colors at the given drawing window, see `ST7735::set_pixels`.
+    pub fn set_pixels<P: IntoIterator<Item = u16>>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: P,
+    ) -> Result<(), ()> {
+        self.display.set_pixels(sx, sy, ex, ey, colors)
+    }
+
+    /// Fills the given drawing window with a single color.
+    pub fn fill_window(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        color: u16,
+    ) -> Result<(), ()> {
+        self.display.fill_window(sx, sy, ex, ey, color)
+    }
+
+    /// Fills the whole display with a single color.
+    pub fn fill_screen(&mut self, color: u16) -> Result<(), ()> {
+        self.display.fill_screen(color)
+    }
+
+    /// Writes a full-screen framebuffer of `PIXELS` colors, row by row.
+    ///
+    /// Returns an error if `framebuffer` has a different length.
+    pub fn write_framebuffer(&mut self, framebuffer: &[u16]) -> Result<(), ()> {
+        if framebuffer.len() != Self::PIXELS || Self::PIXELS == 0 {
+            return Err(());
+        }
+        self.display
+            .set_pixels_buffered(0, 0, W - 1, H - 1, framebuffer.iter().copied())
+            .map(|_| ())
+    }
+}
+
+impl<SPI, DC, RST, const W: u16, const H: u16> Deref for ST7735Sized<SPI, DC, RST, W, H>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    type Target = ST7735<SPI, DC, RST>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.display
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+#[cfg(feature = "graphics")]
+impl<SPI, DC, RST, const W: u16, const H: u16> DrawTarget for ST7735Sized<SPI, DC, RST, W, H>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    type Error = ();
+    type Color = Rgb565;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.display.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.clear(color)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<SPI, DC, RST, const W: u16, const H: u16> OriginDimensions for ST7735Sized<SPI, DC, RST, W, H>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        Size::new(W.into(), H.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::ST7735Sized;
+    use crate::mock::{Bus, Delay};
+
+    use std::vec;
+
+    #[test]
+    fn write_framebuffer_fills_display() {
+        let bus = Bus::default();
+        let mut display: ST7735Sized<_, _, _, 2, 2> =
+            ST7735Sized::new(bus.spi(), bus.dc(), bus.rst(), true, false);
+        display.init(&mut Delay::default()).unwrap();
+        bus.clear();
+
+        assert_eq!(display.write_framebuffer(&[1, 2, 3]), Err(()));
+        display.write_framebuffer(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            bus.commands(),
+            [
+                (0x2A, vec![0, 0, 0, 1]),
+                (0x2B, vec![0, 0, 0, 1]),
+                (0x2C, vec![0, 1, 0, 2, 0, 3, 0, 4]),
+            ]
+        );
+    }
+}
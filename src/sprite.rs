@@ -0,0 +1,201 @@
+use crate::{GRAM_ROWS, ST7735};
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::image::{Image, ImageDrawable};
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::{Dimensions, OriginDimensions, Point, RawData, Size};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::{Drawable, Pixel};
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use micromath::F32Ext;
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Draws a `w` x `h` sprite rotated clockwise by `angle_deg` around `center`.
+    ///
+    /// Every pixel of the destination bounding box is mapped back into the sprite and sampled
+    /// with nearest-neighbor lookup. Pixels outside the sprite or matching `transparent` are
+    /// skipped, and each row is written as runs of visible pixels. This costs a few floating
+    /// point operations per destination pixel, which is slow on MCUs without an FPU, so prefer
+    /// redrawing only when the angle changes.
+    pub fn draw_sprite_rotated(
+        &mut self,
+        sprite: &[u16],
+        w: u16,
+        h: u16,
+        center: Point,
+        angle_deg: f32,
+        transparent: Option<u16>,
+    ) -> Result<(), ()> {
+        if sprite.len() != usize::from(w) * usize::from(h) {
+            return Err(());
+        }
+        let (sin, cos) = F32Ext::sin_cos(angle_deg.to_radians());
+        let (w, h) = (f32::from(w), f32::from(h));
+        let half_width = (w * F32Ext::abs(cos) + h * F32Ext::abs(sin)) / 2.0;
+        let half_height = (w * F32Ext::abs(sin) + h * F32Ext::abs(cos)) / 2.0;
+        let half_width = F32Ext::ceil(half_width) as i32;
+        let half_height = F32Ext::ceil(half_height) as i32;
+
+        let x0 = (center.x - half_width).max(0);
+        let y0 = (center.y - half_height).max(0);
+        let x1 = (center.x + half_width).min(self.width as i32 - 1);
+        let y1 = (center.y + half_height).min(self.height as i32 - 1);
+
+        let sample = |x: i32, y: i32| -> Option<u16> {
+            let dx = (x - center.x) as f32;
+            let dy = (y - center.y) as f32;
+            let sx = cos * dx + sin * dy + w / 2.0;
+            let sy = cos * dy - sin * dx + h / 2.0;
+            if sx < 0.0 || sy < 0.0 || sx >= w || sy >= h {
+                return None;
+            }
+            let color = sprite[sy as usize * w as usize + sx as usize];
+            if Some(color) == transparent {
+                None
+            } else {
+                Some(color)
+            }
+        };
+
+        for y in y0..=y1 {
+            let mut x = x0;
+            while x <= x1 {
+                if sample(x, y).is_none() {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x <= x1 && sample(x, y).is_some() {
+                    x += 1;
+                }
+                self.set_pixels_buffered(
+                    start as u16,
+                    y as u16,
+                    (x - 1) as u16,
+                    y as u16,
+                    (start..x).map(|x| sample(x, y).unwrap_or(0)),
+                )?;
+            }
+        }
+        Ok(())
+    }
+    /// Draws an embedded-graphics image, skipping every pixel that matches `transparent`.
+    ///
+    /// Each row is split into runs of opaque pixels and every run is written to its own window,
+    /// so content behind transparent areas is kept. Fully opaque rows take a single window and
+    /// fully transparent rows send nothing.
+    pub fn draw_image_keyed<T>(
+        &mut self,
+        image: &Image<'_, T>,
+        transparent: Rgb565,
+    ) -> Result<(), ()>
+    where
+        T: ImageDrawable<Color = Rgb565>,
+    {
+        image.draw(&mut Keyed {
+            display: self,
+            transparent,
+        })
+    }
+
+    /// Writes a horizontal run of colors starting at `start`, clipped to the display.
+    fn write_run(&mut self, start: Point, colors: &[u16]) -> Result<(), ()> {
+        if colors.is_empty() || start.y < 0 || start.y >= self.height as i32 {
+            return Ok(());
+        }
+        let skip = (-start.x).max(0) as usize;
+        let end = (self.width as i32 - start.x).clamp(0, colors.len() as i32) as usize;
+        if skip >= end {
+            return Ok(());
+        }
+        let sx = (start.x + skip as i32) as u16;
+        let y = start.y as u16;
+        self.set_pixels_buffered(
+            sx,
+            y,
+            sx + (end - skip) as u16 - 1,
+            y,
+            colors[skip..end].iter().copied(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Draw target that forwards everything but the transparent color to the display.
+struct Keyed<'a, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    display: &'a mut ST7735<SPI, DC, RST>,
+    transparent: Rgb565,
+}
+
+impl<SPI, DC, RST> DrawTarget for Keyed<'_, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    type Color = Rgb565;
+    type Error = ();
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let transparent = self.transparent;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(_, color)| *color != transparent),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        let pixels = width * area.size.height as usize;
+        // Long enough for a full row in any orientation.
+        let mut run = [0; GRAM_ROWS as usize];
+        let mut len = 0;
+        let mut start = area.top_left;
+        for (i, color) in colors.into_iter().take(pixels).enumerate() {
+            let (x, y) = ((i % width) as i32, (i / width) as i32);
+            if x == 0 || color == self.transparent || len == run.len() {
+                self.display.write_run(start, &run[0..len])?;
+                len = 0;
+            }
+            if color == self.transparent {
+                continue;
+            }
+            if len == 0 {
+                start = area.top_left + Point::new(x, y);
+            }
+            run[len] = RawU16::from(color).into_inner();
+            len += 1;
+        }
+        self.display.write_run(start, &run[0..len])
+    }
+}
+
+impl<SPI, DC, RST> OriginDimensions for Keyed<'_, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.display.bounding_box().size
+    }
+}
@@ -0,0 +1,220 @@
+use crate::ST7735;
+
+use embedded_graphics::draw_target::DrawTargetExt;
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyleBuilder};
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::{Point, Size};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::Drawable;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Draws `text` inside the window from (`sx`, `sy`) to (`ex`, `ey`), wrapping lines at spaces.
+    ///
+    /// A line is broken before a word that doesn't fit, and words longer than a full line are
+    /// split between characters. `\n` starts a new line. Lines that don't fit entirely below the
+    /// previous ones are dropped. Returns the number of lines drawn, or an error if the window
+    /// has swapped coordinates (`ex < sx` or `ey < sy`).
+    pub fn draw_text_wrapped(
+        &mut self,
+        text: &str,
+        font: &MonoFont,
+        area: (u16, u16, u16, u16),
+        fg: u16,
+        bg: u16,
+    ) -> Result<u16, ()> {
+        let (sx, sy, ex, ey) = area;
+        if ex < sx || ey < sy {
+            return Err(());
+        }
+        let advance = font.character_size.width + font.character_spacing;
+        let columns = (u32::from(ex - sx) + 1 + font.character_spacing) / advance;
+        let line_height = font.character_size.height;
+        let max_lines = (u32::from(ey - sy) + 1) / line_height;
+        if columns == 0 || max_lines == 0 {
+            return Ok(0);
+        }
+
+        let style = MonoTextStyleBuilder::new()
+            .font(font)
+            .text_color(Rgb565::from(RawU16::new(fg)))
+            .background_color(Rgb565::from(RawU16::new(bg)))
+            .build();
+        let mut lines = 0;
+        wrap_lines(text, columns as usize, |line| {
+            let y = u32::from(sy) + lines * line_height;
+            let position = Point::new(i32::from(sx), y as i32);
+            Text::with_baseline(line, position, style, Baseline::Top).draw(self)?;
+            lines += 1;
+            Ok(lines < max_lines)
+        })?;
+        Ok(lines as u16)
+    }
+
+    /// Draws a single line of `text` centered inside the window from (`sx`, `sy`) to
+    /// (`ex`, `ey`), clipped to the window if it doesn't fit.
+    ///
+    /// Returns the top left corner of the text, which lies outside the window when the text
+    /// overflows it. Like `draw_text_wrapped`, returns an error if the window has swapped
+    /// coordinates.
+    pub fn draw_text_centered(
+        &mut self,
+        text: &str,
+        font: &MonoFont,
+        area: (u16, u16, u16, u16),
+        fg: u16,
+        bg: u16,
+    ) -> Result<Point, ()> {
+        let (sx, sy, ex, ey) = area;
+        if ex < sx || ey < sy {
+            return Err(());
+        }
+        let characters = text.chars().count() as i32;
+        let text_width = characters * font.character_size.width as i32
+            + (characters - 1).max(0) * font.character_spacing as i32;
+        let text_height = font.character_size.height as i32;
+        let area_width = i32::from(ex - sx) + 1;
+        let area_height = i32::from(ey - sy) + 1;
+        let origin = Point::new(
+            i32::from(sx) + (area_width - text_width) / 2,
+            i32::from(sy) + (area_height - text_height) / 2,
+        );
+
+        let style = MonoTextStyleBuilder::new()
+            .font(font)
+            .text_color(Rgb565::from(RawU16::new(fg)))
+            .background_color(Rgb565::from(RawU16::new(bg)))
+            .build();
+        let clip = Rectangle::new(
+            Point::new(i32::from(sx), i32::from(sy)),
+            Size::new(area_width as u32, area_height as u32),
+        );
+        Text::with_baseline(text, origin, style, Baseline::Top).draw(&mut self.clipped(&clip))?;
+        Ok(origin)
+    }
+}
+
+/// Splits `text` into lines of at most `columns` characters, calling `line` for each of them
+/// until it returns `false`.
+fn wrap_lines(
+    text: &str,
+    columns: usize,
+    mut line: impl FnMut(&str) -> Result<bool, ()>,
+) -> Result<(), ()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    for paragraph in text.split('\n') {
+        // Byte range of the current line within the paragraph.
+        let mut current: Option<(usize, usize)> = None;
+        let mut offset = 0;
+        for word in paragraph.split(' ') {
+            let mut start = offset;
+            let mut word = word;
+            offset += word.len() + 1;
+            if word.is_empty() {
+                continue;
+            }
+
+            while word.chars().count() > columns {
+                if let Some((line_start, line_end)) = current.take() {
+                    if !line(&paragraph[line_start..line_end])? {
+                        return Ok(());
+                    }
+                }
+                let (split, _) = word
+                    .char_indices()
+                    .nth(columns)
+                    .unwrap_or((word.len(), ' '));
+                if !line(&word[..split])? {
+                    return Ok(());
+                }
+                start += split;
+                word = &word[split..];
+            }
+
+            let end = start + word.len();
+            current = match current {
+                Some((line_start, _)) if paragraph[line_start..end].chars().count() <= columns => {
+                    Some((line_start, end))
+                }
+                Some((line_start, line_end)) => {
+                    if !line(&paragraph[line_start..line_end])? {
+                        return Ok(());
+                    }
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+
+        let (line_start, line_end) = current.unwrap_or((0, 0));
+        if !line(&paragraph[line_start..line_end])? {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::wrap_lines;
+    use crate::mock;
+
+    use embedded_graphics::mono_font::ascii::FONT_6X10;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    fn wrap(text: &str, columns: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        wrap_lines(text, columns, |line| {
+            lines.push(line.to_string());
+            Ok(true)
+        })
+        .unwrap();
+        lines
+    }
+
+    #[test]
+    fn wrap_lines_breaks_at_spaces_and_splits_long_words() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("abcdefghij kl", 4), ["abcd", "efgh", "ij", "kl"]);
+        assert_eq!(wrap("a\n\nb", 4), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn empty_text_has_no_lines() {
+        assert!(wrap("", 4).is_empty());
+
+        let (mut display, bus) = mock::display();
+        assert_eq!(
+            display.draw_text_wrapped("", &FONT_6X10, (0, 0, 59, 19), 0xFFFF, 0),
+            Ok(0)
+        );
+        assert!(bus.writes().is_empty());
+    }
+
+    #[test]
+    fn swapped_area_is_rejected() {
+        let (mut display, bus) = mock::display();
+        assert_eq!(
+            display.draw_text_wrapped("a", &FONT_6X10, (10, 0, 9, 19), 0xFFFF, 0),
+            Err(())
+        );
+        assert_eq!(
+            display.draw_text_centered("a", &FONT_6X10, (0, 20, 59, 19), 0xFFFF, 0),
+            Err(())
+        );
+        assert!(bus.writes().is_empty());
+    }
+}
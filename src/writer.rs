@@ -0,0 +1,134 @@
+use crate::instruction::Instruction;
+use crate::{PixelFormat, GRAM_ROWS, ST7735};
+
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Streams pixels into a drawing window, collecting them into larger SPI writes.
+///
+/// Created by `ST7735::begin_write`. Pixels still buffered are sent by `finish`. If the writer
+/// is dropped without calling `finish` they are sent as well, but any error is lost, so
+/// prefer calling `finish`.
+///
+/// With `set_mirror_x` enabled every row of the window is collected and sent in reverse, like
+/// the other write paths do.
+pub struct PixelWriter<'a, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    display: &'a mut ST7735<SPI, DC, RST>,
+    buffer: [u8; 32],
+    index: usize,
+    pending: Option<u16>,
+    row: [u16; GRAM_ROWS as usize],
+    row_len: usize,
+}
+
+impl<SPI, DC, RST> ST7735<SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Sets the drawing window and returns a writer to stream pixels into it.
+    pub fn begin_write(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<PixelWriter<'_, SPI, DC, RST>, ()> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, &[])?;
+        self.start_data()?;
+        Ok(PixelWriter {
+            display: self,
+            buffer: [0; 32],
+            index: 0,
+            pending: None,
+            row: [0; GRAM_ROWS as usize],
+            row_len: 0,
+        })
+    }
+}
+
+impl<SPI, DC, RST> PixelWriter<'_, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Adds a pixel color, sending the buffer when it is full.
+    pub fn write(&mut self, color: u16) -> Result<(), ()> {
+        if !self.display.mirror_x {
+            return self.push(color);
+        }
+        self.row[self.row_len] = color;
+        self.row_len += 1;
+        if self.row_len >= usize::from(self.display.window_width).clamp(1, self.row.len()) {
+            self.flush_row()?;
+        }
+        Ok(())
+    }
+
+    /// Adds the collected row of a mirrored window in reverse.
+    fn flush_row(&mut self) -> Result<(), ()> {
+        while self.row_len > 0 {
+            self.row_len -= 1;
+            self.push(self.row[self.row_len])?;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, color: u16) -> Result<(), ()> {
+        let (bytes, len) = self.display.encode_color(color, &mut self.pending);
+        if self.index + len > self.buffer.len() {
+            self.flush()?;
+        }
+        self.buffer[self.index..self.index + len].copy_from_slice(&bytes[0..len]);
+        self.index += len;
+        Ok(())
+    }
+
+    /// Adds all pixel colors of an iterator.
+    pub fn write_all<P: IntoIterator<Item = u16>>(&mut self, colors: P) -> Result<(), ()> {
+        colors.into_iter().try_for_each(|color| self.write(color))
+    }
+
+    /// Sends the remaining buffered pixels and ends the write.
+    pub fn finish(mut self) -> Result<(), ()> {
+        self.flush_all()
+    }
+
+    fn flush(&mut self) -> Result<(), ()> {
+        if self.index > 0 {
+            self.display.write_data(&self.buffer[0..self.index])?;
+            self.index = 0;
+        }
+        Ok(())
+    }
+
+    fn flush_all(&mut self) -> Result<(), ()> {
+        self.flush_row()?;
+        let (bytes, len) = PixelFormat::encode_pending(self.pending.take());
+        if self.index + len > self.buffer.len() {
+            self.flush()?;
+        }
+        self.buffer[self.index..self.index + len].copy_from_slice(&bytes[0..len]);
+        self.index += len;
+        self.flush()
+    }
+}
+
+impl<SPI, DC, RST> Drop for PixelWriter<'_, SPI, DC, RST>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    fn drop(&mut self) {
+        let _ = self.flush_all();
+    }
+}